@@ -0,0 +1,304 @@
+use solana_program::{
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    pubkey::Pubkey,
+};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+use crate::{
+    instructions::{sync::SyncInstruction, Instruction},
+    state::{
+        approval::Approval,
+        bond::Bond,
+        config::{ConfigUpdate, ProgramConfig},
+        features::{Feature, FeaturesSet},
+        record::ErRecord,
+        registry::{FeatureDefinition, FeatureRegistry},
+        status::Suspension,
+    },
+    ID,
+};
+
+/// Builds an instruction which registers ER node in domain registry,
+/// the ER identity acts both as the signer and the rent payer
+pub fn register_ix(record: ErRecord) -> SolanaInstruction {
+    let mut accounts = accounts(*record.identity(), record.pda().0);
+    accounts.extend(admission_accounts(record.identity()));
+    accounts.push(AccountMeta::new_readonly(FeatureRegistry::pda().0, false));
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Register(record), accounts)
+}
+
+/// Builds an instruction which synchronizes updated ER information with its registry record,
+/// the ER identity acts both as the signer and the payer of the possible rent top-up
+pub fn sync_ix(sync: SyncInstruction) -> SolanaInstruction {
+    let mut accounts = accounts(*sync.identity(), sync.pda());
+    accounts.extend(policy_accounts());
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Sync(sync), accounts)
+}
+
+/// Same as [`sync_ix`], but the transaction is signed by the record's authority,
+/// which also pays for the possible rent top-up
+pub fn authority_sync_ix(authority: Pubkey, sync: SyncInstruction) -> SolanaInstruction {
+    let mut accounts = accounts(authority, sync.pda());
+    accounts.extend(policy_accounts());
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Sync(sync), accounts)
+}
+
+/// Builds an instruction which removes ER node record from domain registry,
+/// the rent lamports are returned back to the ER identity
+pub fn unregister_ix(identity: Pubkey) -> SolanaInstruction {
+    let accounts = accounts(identity, ErRecord::pda_for(&identity).0);
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Unregister(identity), accounts)
+}
+
+/// Same as [`unregister_ix`], but the transaction is signed by the record's authority,
+/// which also receives the rent lamports
+pub fn authority_unregister_ix(authority: Pubkey, identity: Pubkey) -> SolanaInstruction {
+    let accounts = accounts(authority, ErRecord::pda_for(&identity).0);
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Unregister(identity), accounts)
+}
+
+/// Builds an instruction which removes ER node record from domain registry, the rent lamports
+/// are sent to the recipient, the signer is either the ER identity or the record's authority
+pub fn unregister_with_recipient_ix(
+    signer: Pubkey,
+    identity: Pubkey,
+    recipient: Pubkey,
+) -> SolanaInstruction {
+    let mut accounts = accounts(signer, ErRecord::pda_for(&identity).0);
+    accounts.push(AccountMeta::new(recipient, false));
+    let ix = Instruction::UnregisterWithRecipient {
+        identity,
+        recipient,
+    };
+    SolanaInstruction::new_with_borsh(ID, &ix, accounts)
+}
+
+/// Builds an instruction which migrates ER node record to the latest record version,
+/// the ER identity acts both as the signer and the payer of the possible rent top-up
+pub fn migrate_ix(identity: Pubkey) -> SolanaInstruction {
+    let accounts = accounts(identity, ErRecord::pda_for(&identity).0);
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Migrate(identity), accounts)
+}
+
+/// Builds an instruction which refreshes the liveness of ER node record,
+/// the ER identity has to sign the transaction and pays for the legacy record migration
+pub fn heartbeat_ix(identity: Pubkey) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(identity, true),
+        AccountMeta::new(ErRecord::pda_for(&identity).0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Heartbeat(identity), accounts)
+}
+
+/// Builds an instruction which removes the expired record of ER node with the given identity,
+/// the caller receives a bounty and the rest of the rent is returned to the ER identity
+pub fn reap_ix(caller: Pubkey, identity: Pubkey) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(caller, true),
+        AccountMeta::new(ErRecord::pda_for(&identity).0, false),
+        AccountMeta::new(identity, false),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Reap(identity), accounts)
+}
+
+/// Builds an instruction which moves ER node record to the PDA of the new identity,
+/// both identities have to sign the transaction, the old one pays for the new record
+/// and receives the rent of the old record back
+pub fn rotate_identity_ix(identity: Pubkey, new_identity: Pubkey) -> SolanaInstruction {
+    let mut accounts = vec![
+        AccountMeta::new(identity, true),
+        AccountMeta::new_readonly(new_identity, true),
+        AccountMeta::new(ErRecord::pda_for(&identity).0, false),
+        AccountMeta::new(ErRecord::pda_for(&new_identity).0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(admission_accounts(&new_identity));
+    let ix = Instruction::RotateIdentity {
+        identity,
+        new_identity,
+    };
+    SolanaInstruction::new_with_borsh(ID, &ix, accounts)
+}
+
+/// Builds an instruction which creates the program config with the default policy, the
+/// signer (the upgrade authority of the program) pays for the config account and becomes its admin
+pub fn init_config_ix(admin: Pubkey) -> SolanaInstruction {
+    let mut accounts = accounts(admin, ProgramConfig::pda().0);
+    let program_data = ProgramConfig::program_data_address();
+    accounts.push(AccountMeta::new_readonly(program_data, false));
+    SolanaInstruction::new_with_borsh(ID, &Instruction::InitConfig, accounts)
+}
+
+/// Builds an instruction which modifies the program config, signed by its admin
+pub fn update_config_ix(admin: Pubkey, update: ConfigUpdate) -> SolanaInstruction {
+    let accounts = accounts(admin, ProgramConfig::pda().0);
+    SolanaInstruction::new_with_borsh(ID, &Instruction::UpdateConfig(update), accounts)
+}
+
+/// Builds an instruction which completes the admin transfer, signed by the pending admin
+pub fn accept_config_admin_ix(new_admin: Pubkey) -> SolanaInstruction {
+    let accounts = accounts(new_admin, ProgramConfig::pda().0);
+    SolanaInstruction::new_with_borsh(ID, &Instruction::AcceptConfigAdmin, accounts)
+}
+
+/// Builds an instruction which approves ER node with the given identity to register
+/// in permissioned mode, the program admin signs and pays for the approval account
+pub fn grant_approval_ix(admin: Pubkey, identity: Pubkey) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(admin, true),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new(Approval::pda_for(&identity).0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::GrantApproval(identity), accounts)
+}
+
+/// Builds an instruction which revokes the approval of ER node with the given
+/// identity, the program admin signs and receives the rent of the approval account
+pub fn revoke_approval_ix(admin: Pubkey, identity: Pubkey) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(admin, true),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new(Approval::pda_for(&identity).0, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::RevokeApproval(identity), accounts)
+}
+
+/// Builds an instruction which suspends ER node with the given identity, signed by the program
+/// admin, who also pays for the possible rent top-up due to the record migration
+pub fn suspend_ix(admin: Pubkey, identity: Pubkey, suspension: Suspension) -> SolanaInstruction {
+    let ix = Instruction::Suspend {
+        identity,
+        suspension,
+    };
+    SolanaInstruction::new_with_borsh(ID, &ix, suspension_accounts(admin, &identity))
+}
+
+/// Builds an instruction which lifts the suspension of ER node with the given identity
+pub fn unsuspend_ix(admin: Pubkey, identity: Pubkey) -> SolanaInstruction {
+    let accounts = suspension_accounts(admin, &identity);
+    SolanaInstruction::new_with_borsh(ID, &Instruction::Unsuspend(identity), accounts)
+}
+
+/// Builds an instruction which adds the given amount of lamports to the stake bond of ER node,
+/// the funder signs the transaction and pays for the vault creation if necessary
+pub fn deposit_bond_ix(funder: Pubkey, identity: Pubkey, amount: u64) -> SolanaInstruction {
+    let accounts = accounts(funder, Bond::pda_for(&identity).0);
+    let ix = Instruction::DepositBond { identity, amount };
+    SolanaInstruction::new_with_borsh(ID, &ix, accounts)
+}
+
+/// Builds an instruction which starts the cooldown of the stake bond of unregistered
+/// ER node, the ER identity signs and pays for the possible rent top-up
+pub fn unlock_bond_ix(identity: Pubkey) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(identity, true),
+        AccountMeta::new(Bond::pda_for(&identity).0, false),
+        AccountMeta::new_readonly(ErRecord::pda_for(&identity).0, false),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::UnlockBond(identity), accounts)
+}
+
+/// Builds an instruction which returns the unlocked stake bond to the ER identity
+pub fn withdraw_bond_ix(identity: Pubkey) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(identity, true),
+        AccountMeta::new(Bond::pda_for(&identity).0, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::WithdrawBond(identity), accounts)
+}
+
+/// Makes the given register, sync or unregister instruction use a separate payer, which
+/// has to sign the transaction, covers the rent and receives the refunds instead of the signer
+pub fn with_payer(mut ix: SolanaInstruction, payer: Pubkey) -> SolanaInstruction {
+    // the payer follows the required accounts, preceding the program config
+    let config = ProgramConfig::pda().0;
+    let i = ix
+        .accounts
+        .iter()
+        .position(|meta| meta.pubkey == config)
+        .unwrap_or(ix.accounts.len());
+    ix.accounts.insert(i, AccountMeta::new(payer, true));
+    ix
+}
+
+/// Builds an instruction which defines new feature in the feature registry, signed by the
+/// program admin, who pays for the registry creation on the first definition
+pub fn define_feature_ix(admin: Pubkey, feature: FeatureDefinition) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new(admin, true),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new(FeatureRegistry::pda().0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::DefineFeature(feature), accounts)
+}
+
+/// Builds an instruction which deprecates the feature with the given bit index,
+/// the program admin has to sign the transaction
+pub fn deprecate_feature_ix(admin: Pubkey, bit: u8) -> SolanaInstruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new(FeatureRegistry::pda().0, false),
+    ];
+    SolanaInstruction::new_with_borsh(ID, &Instruction::DeprecateFeature(bit), accounts)
+}
+
+/// Renders the enabled features as a comma separated list of their names, taken from the
+/// feature registry, deprecated features are marked as such, while the ones missing from
+/// the registry fall back to [`Feature`] display
+pub fn render_features(registry: &FeatureRegistry, features: &FeaturesSet) -> String {
+    let render = |bit| match registry.get(bit) {
+        Some(f) if f.active => f.name.clone(),
+        Some(f) => format!("{} (deprecated)", f.name),
+        None => Feature::from(bit).to_string(),
+    };
+    features
+        .iter_bits()
+        .map(render)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Program config, approval and bond of the given identity, required to check whether
+/// the identity is allowed to register, e.g. in permissioned mode or if the bond is required
+fn admission_accounts(identity: &Pubkey) -> [AccountMeta; 3] {
+    [
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new_readonly(Approval::pda_for(identity).0, false),
+        AccountMeta::new_readonly(Bond::pda_for(identity).0, false),
+    ]
+}
+
+/// Program config and feature registry, required to check whether the features
+/// of the synced record are defined, if the policy calls for it
+fn policy_accounts() -> [AccountMeta; 2] {
+    [
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new_readonly(FeatureRegistry::pda().0, false),
+    ]
+}
+
+/// Account list shared by the suspension management instructions
+fn suspension_accounts(admin: Pubkey, identity: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(admin, true),
+        AccountMeta::new_readonly(ProgramConfig::pda().0, false),
+        AccountMeta::new(ErRecord::pda_for(identity).0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ]
+}
+
+/// Account list shared by all the record management instructions
+fn accounts(identity: Pubkey, pda: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(identity, true),
+        AccountMeta::new(pda, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ]
+}
@@ -1,2 +1,76 @@
 /// Seed string used in PDA derivation for domain registry record
 pub const ER_RECORD_SEED: &[u8] = b"er-record";
+
+/// Prefix of every ER record account data, can be used to filter program
+/// accounts with memcmp at offset 0. Records created before its introduction
+/// start directly with the `ErRecord` variant index, which never collides
+/// with the first (ASCII) byte of the discriminator.
+pub const ER_RECORD_DISCRIMINATOR: [u8; 8] = *b"ERRECORD";
+
+/// Number of slots (roughly 1 hour) after the last sync or heartbeat,
+/// once elapsed the ER node should be considered stale by the clients
+pub const DEFAULT_RECORD_TTL_SLOTS: u64 = 9_000;
+
+/// Number of slots (roughly 1 day) after the record has become stale,
+/// once elapsed anybody can reap the record and collect the bounty
+pub const DEFAULT_REAP_GRACE_SLOTS: u64 = 216_000;
+
+/// Share of the record's rent (in basis points) paid to whoever reaps the expired record
+pub const DEFAULT_REAP_BOUNTY_BPS: u16 = 500;
+
+/// Maximum length in bytes of the ER node address
+pub const MAX_ADDR_LEN: usize = 256;
+
+/// URL schemes which are allowed in the ER node address, as well as in JSON-RPC endpoints
+pub const ALLOWED_ADDR_SCHEMES: &[&str] = &["https", "wss"];
+
+/// URL schemes which are allowed in websocket endpoints
+pub const ALLOWED_WS_SCHEMES: &[&str] = &["wss"];
+
+/// URL schemes which are allowed in gRPC endpoints, plaintext HTTP/2 included
+pub const ALLOWED_GRPC_SCHEMES: &[&str] = &["https", "http"];
+
+/// URL schemes which are allowed in monitoring metrics endpoints
+pub const ALLOWED_METRICS_SCHEMES: &[&str] = &["https", "http"];
+
+/// URL schemes which are allowed in application specific endpoints
+pub const ALLOWED_CUSTOM_SCHEMES: &[&str] = &["https", "http", "wss", "ws"];
+
+/// Maximum number of service endpoints a single ER record can hold
+pub const MAX_ENDPOINTS: usize = 16;
+
+/// Maximum length in bytes of the cloud region or datacenter identifier of ER node
+pub const MAX_REGION_LEN: usize = 64;
+
+/// Seed string used in PDA derivation for the feature registry
+pub const FEATURE_REGISTRY_SEED: &[u8] = b"feature-registry";
+
+/// Prefix of the feature registry account data
+pub const FEATURE_REGISTRY_DISCRIMINATOR: [u8; 8] = *b"FEATURES";
+
+/// Maximum length in bytes of the canonical feature name
+pub const MAX_FEATURE_NAME_LEN: usize = 32;
+
+/// Maximum length in bytes of the feature description
+pub const MAX_FEATURE_DESCRIPTION_LEN: usize = 256;
+
+/// Seed string used in PDA derivation for the program config
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Prefix of the program config account data
+pub const CONFIG_DISCRIMINATOR: [u8; 8] = *b"MDPCONFG";
+
+/// Seed string used in PDA derivation for the approval of ER node in permissioned mode
+pub const APPROVAL_SEED: &[u8] = b"approval";
+
+/// Prefix of the approval account data
+pub const APPROVAL_DISCRIMINATOR: [u8; 8] = *b"APPROVAL";
+
+/// Number of slots (roughly 1 day) the bond stays locked after the unlock request
+pub const DEFAULT_BOND_COOLDOWN_SLOTS: u64 = 216_000;
+
+/// Seed string used in PDA derivation for the stake bond vault of ER node operator
+pub const BOND_SEED: &[u8] = b"bond";
+
+/// Prefix of the stake bond vault account data
+pub const BOND_DISCRIMINATOR: [u8; 8] = *b"STAKEBND";
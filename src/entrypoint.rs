@@ -21,6 +21,44 @@ pub fn process<'a>(
     match ix {
         Instruction::Register(record) => register::process_registration(accounts, record),
         Instruction::Sync(ix) => sync::process_sync_record(accounts, ix),
-        Instruction::Unregister(node_id) => unregister::process_unregistration(accounts, node_id),
+        Instruction::Unregister(node_id) => {
+            unregister::process_unregistration(accounts, node_id, None)
+        }
+        Instruction::Migrate(node_id) => migrate::process_migration(accounts, node_id),
+        Instruction::Heartbeat(node_id) => heartbeat::process_heartbeat(accounts, node_id),
+        Instruction::Reap(node_id) => reap::process_reap(accounts, node_id),
+        Instruction::RotateIdentity {
+            identity,
+            new_identity,
+        } => rotate::process_identity_rotation(accounts, identity, new_identity),
+        Instruction::UnregisterWithRecipient {
+            identity,
+            recipient,
+        } => unregister::process_unregistration(accounts, identity, Some(recipient)),
+        Instruction::DefineFeature(feature) => {
+            features::process_feature_definition(accounts, feature)
+        }
+        Instruction::DeprecateFeature(bit) => features::process_feature_deprecation(accounts, bit),
+        Instruction::InitConfig => config::process_config_init(accounts),
+        Instruction::UpdateConfig(update) => config::process_config_update(accounts, update),
+        Instruction::AcceptConfigAdmin => config::process_admin_acceptance(accounts),
+        Instruction::GrantApproval(identity) => {
+            approval::process_approval_grant(accounts, identity)
+        }
+        Instruction::RevokeApproval(identity) => {
+            approval::process_approval_revocation(accounts, identity)
+        }
+        Instruction::Suspend {
+            identity,
+            suspension,
+        } => suspension::process_suspension(accounts, identity, Some(suspension)),
+        Instruction::Unsuspend(identity) => {
+            suspension::process_suspension(accounts, identity, None)
+        }
+        Instruction::DepositBond { identity, amount } => {
+            bond::process_bond_deposit(accounts, identity, amount)
+        }
+        Instruction::UnlockBond(identity) => bond::process_bond_unlock(accounts, identity),
+        Instruction::WithdrawBond(identity) => bond::process_bond_withdrawal(accounts, identity),
     }
 }
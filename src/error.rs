@@ -0,0 +1,190 @@
+use std::fmt;
+
+use solana_program::{instruction::InstructionError, program_error::ProgramError};
+use solana_transaction_error::TransactionError;
+
+/// Errors returned by the domain registration program
+///
+/// Every variant is converted into `ProgramError::Custom(code)`, where code
+/// is the explicit discriminant of the variant. The codes are part of the
+/// public interface and must never be reused or renumbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum MdpError {
+    /// account which should have authorized the operation hasn't signed the transaction
+    MissingSignature = 0,
+    /// signer of the transaction is not the identity of the ER node
+    IdentityMismatch = 1,
+    /// provided record account doesn't match the PDA derived from ER node identity
+    PdaMismatch = 2,
+    /// provided record account is not owned by the program
+    InvalidRecordOwner = 3,
+    /// record for the given ER node has already been registered
+    RecordAlreadyExists = 4,
+    /// record for the given ER node doesn't exist
+    RecordNotFound = 5,
+    /// data stored in record account cannot be deserialized
+    InvalidRecordData = 6,
+    /// lamports balance overflowed during the transfer
+    LamportsOverflow = 7,
+    /// sequence number of the record doesn't match the expected one
+    SeqMismatch = 8,
+    /// operation requires the record to be migrated to the latest version
+    OutdatedRecordVersion = 9,
+    /// record is still within its liveness and grace period
+    RecordNotExpired = 10,
+    /// provided account doesn't match the recipient specified in the instruction
+    RecipientMismatch = 11,
+    /// ER node address is not a valid URL with allowed scheme
+    InvalidAddr = 12,
+    /// ER node endpoints are malformed, duplicated or lack JSON-RPC endpoint
+    InvalidEndpoint = 13,
+    /// ER node endpoint to be modified doesn't exist in the record
+    EndpointNotFound = 14,
+    /// country code is not a valid ISO 3166-1 numeric or alpha-3 code
+    InvalidCountryCode = 15,
+    /// geographic coordinates are out of range or region identifier is malformed
+    InvalidLocation = 16,
+    /// feature name or bit index can't be parsed, or feature definition is malformed
+    InvalidFeature = 17,
+    /// transaction signer is not the admin of the program
+    AdminMismatch = 18,
+    /// feature bit is not defined in the feature registry
+    UndefinedFeature = 19,
+    /// feature with the same bit index or name is already defined in the feature registry
+    FeatureAlreadyDefined = 20,
+    /// program config has already been initialized
+    ConfigAlreadyInitialized = 21,
+    /// program config values are out of range
+    InvalidConfig = 22,
+    /// ER node identity hasn't been approved by the admin, while the program is in permissioned mode
+    NotApproved = 23,
+    /// ER node identity has already been approved
+    AlreadyApproved = 24,
+    /// ER node has been suspended by the admin
+    Suspended = 25,
+    /// ER node operator hasn't bonded the minimum amount of lamports required for registration
+    InsufficientBond = 26,
+    /// bond is still locked, either because ER node is registered or the cooldown hasn't elapsed
+    BondLocked = 27,
+    /// bond deposit amount is zero
+    InvalidBondAmount = 28,
+}
+
+impl MdpError {
+    /// Returns the stable numeric code of the error
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Extracts program error from the transaction error, if the latter was caused by the program
+    pub fn from_transaction_error(error: &TransactionError) -> Option<Self> {
+        match error {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                Self::try_from(*code).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<u32> for MdpError {
+    type Error = u32;
+
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        let error = match code {
+            0 => Self::MissingSignature,
+            1 => Self::IdentityMismatch,
+            2 => Self::PdaMismatch,
+            3 => Self::InvalidRecordOwner,
+            4 => Self::RecordAlreadyExists,
+            5 => Self::RecordNotFound,
+            6 => Self::InvalidRecordData,
+            7 => Self::LamportsOverflow,
+            8 => Self::SeqMismatch,
+            9 => Self::OutdatedRecordVersion,
+            10 => Self::RecordNotExpired,
+            11 => Self::RecipientMismatch,
+            12 => Self::InvalidAddr,
+            13 => Self::InvalidEndpoint,
+            14 => Self::EndpointNotFound,
+            15 => Self::InvalidCountryCode,
+            16 => Self::InvalidLocation,
+            17 => Self::InvalidFeature,
+            18 => Self::AdminMismatch,
+            19 => Self::UndefinedFeature,
+            20 => Self::FeatureAlreadyDefined,
+            21 => Self::ConfigAlreadyInitialized,
+            22 => Self::InvalidConfig,
+            23 => Self::NotApproved,
+            24 => Self::AlreadyApproved,
+            25 => Self::Suspended,
+            26 => Self::InsufficientBond,
+            27 => Self::BondLocked,
+            28 => Self::InvalidBondAmount,
+            _ => return Err(code),
+        };
+        Ok(error)
+    }
+}
+
+impl From<MdpError> for ProgramError {
+    fn from(error: MdpError) -> Self {
+        Self::Custom(error.code())
+    }
+}
+
+impl fmt::Display for MdpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::MissingSignature => "required signature is missing",
+            Self::IdentityMismatch => "signer is not the ER node identity",
+            Self::PdaMismatch => "record account doesn't match derived PDA",
+            Self::InvalidRecordOwner => "record account is not owned by the program",
+            Self::RecordAlreadyExists => "ER record has already been registered",
+            Self::RecordNotFound => "ER record doesn't exist",
+            Self::InvalidRecordData => "ER record data is malformed",
+            Self::LamportsOverflow => "lamports balance overflow",
+            Self::SeqMismatch => "ER record sequence number doesn't match the expected one",
+            Self::OutdatedRecordVersion => "ER record has to be migrated to the latest version",
+            Self::RecordNotExpired => "ER record hasn't expired yet",
+            Self::RecipientMismatch => "recipient account doesn't match the specified one",
+            Self::InvalidAddr => "ER node address is malformed",
+            Self::InvalidEndpoint => "ER node endpoints are invalid",
+            Self::EndpointNotFound => "ER node endpoint doesn't exist",
+            Self::InvalidCountryCode => "country code is not a valid ISO 3166-1 code",
+            Self::InvalidLocation => "ER node location is invalid",
+            Self::InvalidFeature => "feature name, bit index or definition is malformed",
+            Self::AdminMismatch => "transaction signer is not the program admin",
+            Self::UndefinedFeature => "feature is not defined in the feature registry",
+            Self::FeatureAlreadyDefined => "feature is already defined in the feature registry",
+            Self::ConfigAlreadyInitialized => "program config has already been initialized",
+            Self::InvalidConfig => "program config values are out of range",
+            Self::NotApproved => "ER node identity hasn't been approved",
+            Self::AlreadyApproved => "ER node identity has already been approved",
+            Self::Suspended => "ER node has been suspended by the admin",
+            Self::InsufficientBond => "ER node operator hasn't bonded enough lamports",
+            Self::BondLocked => "bond is still locked",
+            Self::InvalidBondAmount => "bond deposit amount should be positive",
+        };
+        write!(f, "{msg} (code {})", self.code())
+    }
+}
+
+impl std::error::Error for MdpError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_roundtrip() {
+        for code in 0..=MdpError::InvalidBondAmount.code() {
+            let error = MdpError::try_from(code).unwrap();
+            assert_eq!(error.code(), code);
+            let tx_error = TransactionError::InstructionError(0, InstructionError::Custom(code));
+            assert_eq!(MdpError::from_transaction_error(&tx_error), Some(error));
+        }
+        assert!(MdpError::try_from(MdpError::InvalidBondAmount.code() + 1).is_err());
+    }
+}
@@ -2,7 +2,9 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 use sync::SyncInstruction;
 
-use crate::state::record::ErRecord;
+use crate::state::{
+    config::ConfigUpdate, record::ErRecord, registry::FeatureDefinition, status::Suspension,
+};
 
 pub mod sync;
 pub mod version;
@@ -13,4 +15,53 @@ pub enum Instruction {
     Register(ErRecord),
     Unregister(Pubkey),
     Sync(SyncInstruction),
+    /// Converts record of the given ER node into the latest record version
+    Migrate(Pubkey),
+    /// Refreshes the liveness of the given ER node record
+    Heartbeat(Pubkey),
+    /// Removes the record of the given ER node, which has expired, rewarding the caller
+    Reap(Pubkey),
+    /// Moves the record of the given ER node to the PDA of its new identity, keeping its history
+    RotateIdentity {
+        identity: Pubkey,
+        new_identity: Pubkey,
+    },
+    /// Same as `Unregister`, but the reclaimed rent is sent to the given recipient
+    UnregisterWithRecipient {
+        identity: Pubkey,
+        recipient: Pubkey,
+    },
+    /// Defines new feature in the feature registry, creating the registry if it doesn't exist,
+    /// only the program admin can do it
+    DefineFeature(FeatureDefinition),
+    /// Deprecates the feature with the given bit index in the feature registry, only the
+    /// program admin can do it
+    DeprecateFeature(u8),
+    /// Creates the program config with the default policy, making the signer its admin,
+    /// the signer has to be the upgrade authority of the program
+    InitConfig,
+    /// Modifies the program config, only the admin can do it
+    UpdateConfig(ConfigUpdate),
+    /// Completes the transfer of the admin role, signed by the pending admin
+    AcceptConfigAdmin,
+    /// Approves ER node with the given identity to register in permissioned mode
+    GrantApproval(Pubkey),
+    /// Revokes the approval of ER node with the given identity
+    RevokeApproval(Pubkey),
+    /// Suspends the given ER node, the operator can't lift the suspension, only the admin can
+    Suspend {
+        identity: Pubkey,
+        suspension: Suspension,
+    },
+    /// Lifts the suspension of the given ER node
+    Unsuspend(Pubkey),
+    /// Adds lamports to the stake bond of the given ER node, creating the vault if necessary
+    DepositBond {
+        identity: Pubkey,
+        amount: u64,
+    },
+    /// Starts the cooldown of the bond of the given ER node, once it has been unregistered
+    UnlockBond(Pubkey),
+    /// Returns the bond of the given ER node to its identity, once the cooldown has elapsed
+    WithdrawBond(Pubkey),
 }
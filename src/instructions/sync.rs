@@ -2,30 +2,45 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
 use crate::{
-    consts::ER_RECORD_SEED,
-    state::{features::FeaturesSet, record::CountryCode, status::ErStatus},
-    ID,
+    error::MdpError,
+    state::{
+        addr::validate_addr,
+        endpoint::EndpointUpdate,
+        features::FeaturesSet,
+        location::{validate_region, Coordinates},
+        record::{CountryCode, ErRecord},
+        status::ErStatus,
+    },
 };
 
-use super::version::v0::SyncRecordV0;
+use super::version::{v0::SyncRecordV0, v1::SyncRecordV1};
 
 /// Versioned sync program instruction
 #[derive(BorshSerialize, BorshDeserialize)]
 pub enum SyncInstruction {
     V0(SyncRecordV0),
+    V1(SyncRecordV1),
 }
 
 impl SyncInstruction {
     /// Compute the record PDA for given ER node identity
     pub fn pda(&self) -> Pubkey {
-        let seeds = [ER_RECORD_SEED, self.identity().as_ref()];
-        Pubkey::find_program_address(&seeds, &ID).0
+        ErRecord::pda_for(self.identity()).0
     }
 
     /// Returns identity pubkey of the ER node
     pub fn identity(&self) -> &Pubkey {
         match self {
             Self::V0(r) => &r.identity,
+            Self::V1(r) => &r.identity,
+        }
+    }
+
+    /// Returns the sequence number the record is expected to have, if set
+    pub fn expected_seq(&self) -> Option<u64> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.expected_seq,
         }
     }
 
@@ -33,6 +48,7 @@ impl SyncInstruction {
     pub fn addr(&mut self) -> &mut Option<String> {
         match self {
             Self::V0(v) => &mut v.addr,
+            Self::V1(v) => &mut v.addr,
         }
     }
 
@@ -40,6 +56,7 @@ impl SyncInstruction {
     pub fn base_fee(&mut self) -> &mut Option<u16> {
         match self {
             Self::V0(v) => &mut v.base_fee,
+            Self::V1(v) => &mut v.base_fee,
         }
     }
 
@@ -47,6 +64,7 @@ impl SyncInstruction {
     pub fn block_time_ms(&mut self) -> &mut Option<u16> {
         match self {
             Self::V0(v) => &mut v.block_time_ms,
+            Self::V1(v) => &mut v.block_time_ms,
         }
     }
 
@@ -54,6 +72,7 @@ impl SyncInstruction {
     pub fn features(&mut self) -> &mut Option<FeaturesSet> {
         match self {
             Self::V0(v) => &mut v.features,
+            Self::V1(v) => &mut v.features,
         }
     }
 
@@ -61,6 +80,7 @@ impl SyncInstruction {
     pub fn status(&mut self) -> &mut Option<ErStatus> {
         match self {
             Self::V0(v) => &mut v.status,
+            Self::V1(v) => &mut v.status,
         }
     }
 
@@ -68,12 +88,223 @@ impl SyncInstruction {
     pub fn load_average(&mut self) -> &mut Option<u32> {
         match self {
             Self::V0(v) => &mut v.load_average,
+            Self::V1(v) => &mut v.load_average,
         }
     }
 
+    /// Takes the endpoint modifications out of the instruction, empty for versions without them
+    pub fn take_endpoint_updates(&mut self) -> Vec<EndpointUpdate> {
+        match self {
+            Self::V0(_) => Vec::new(),
+            Self::V1(v) => std::mem::take(&mut v.endpoints),
+        }
+    }
+
+    /// Returns the new authority of the record, if set
+    pub fn authority(&self) -> Option<&Pubkey> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.authority.as_ref(),
+        }
+    }
+
+    /// Takes geographic coordinates of ER node out of the instruction, if set,
+    /// the inner `None` means that the coordinates should be cleared
+    pub fn take_coordinates(&mut self) -> Option<Option<Coordinates>> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.coordinates.take(),
+        }
+    }
+
+    /// Takes cloud region or datacenter identifier of ER node out of the instruction, if set,
+    /// the inner `None` means that the region should be cleared
+    pub fn take_region(&mut self) -> Option<Option<String>> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.region.take(),
+        }
+    }
+
+    /// Returns ISO 3166-1 country code, where ER node is deployed, if set
     pub fn country_code(&mut self) -> &mut Option<CountryCode> {
         match self {
             Self::V0(v) => &mut v.country_code,
+            Self::V1(v) => &mut v.country_code,
         }
     }
+
+    /// Applies every field set in the instruction to the record, the fields managed
+    /// by the program itself (identity, freshness information) are left intact
+    pub fn apply(mut self, record: &mut ErRecord) -> Result<(), MdpError> {
+        if let Some(authority) = self.authority() {
+            record.set_authority(*authority)?;
+        }
+        if let Some(status) = self.status().take() {
+            record.set_status(status);
+        }
+        if let Some(block_time_ms) = self.block_time_ms().take() {
+            record.set_block_time_ms(block_time_ms);
+        }
+        if let Some(base_fee) = self.base_fee().take() {
+            record.set_base_fee(base_fee);
+        }
+        if let Some(features) = self.features().take() {
+            record.set_features(features);
+        }
+        if let Some(load_average) = self.load_average().take() {
+            record.set_load_average(load_average);
+        }
+        if let Some(country_code) = self.country_code().take() {
+            country_code.validate()?;
+            record.set_country_code(country_code);
+        }
+        if let Some(coordinates) = self.take_coordinates() {
+            if let Some(coordinates) = coordinates {
+                coordinates.validate()?;
+            }
+            record.set_coordinates(coordinates)?;
+        }
+        if let Some(region) = self.take_region() {
+            if let Some(region) = &region {
+                validate_region(region)?;
+            }
+            record.set_region(region)?;
+        }
+        if let Some(addr) = self.addr().take() {
+            validate_addr(&addr)?;
+            record.set_addr(addr);
+        }
+        let updates = self.take_endpoint_updates();
+        if !updates.is_empty() {
+            for update in updates {
+                record.update_endpoint(update)?;
+            }
+            record.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::state::{
+        endpoint::{Endpoint, ServiceKind},
+        features::Feature,
+        location::Coordinates,
+        status::Suspension,
+        version::v1::RecordV1,
+    };
+
+    /// Every field of the latest record version is either destructured below as synced,
+    /// or explicitly marked as managed by the program or the admin, so new fields can't be skipped
+    #[test]
+    fn test_sync_applies_every_field() {
+        let before = RecordV1 {
+            identity: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            previous_identity: Some(Pubkey::new_unique()),
+            status: ErStatus::Active,
+            block_time_ms: 50,
+            base_fee: 1000,
+            features: FeaturesSet::default(),
+            load_average: 1_000_000,
+            country_code: CountryCode::try_from("DEU").unwrap(),
+            coordinates: None,
+            region: None,
+            registered_at: 1,
+            last_sync_slot: 2,
+            last_sync_unix_ts: 3,
+            seq: 4,
+            endpoints: vec![Endpoint::rpc("https://rpc.er.app".to_string())],
+            suspension: Some(Suspension {
+                reason: 1,
+                expires_at: None,
+            }),
+        };
+        assert!(ErRecord::V1(before.clone()).is_latest());
+
+        let RecordV1 {
+            // managed by the program
+            identity: _,
+            previous_identity: _,
+            registered_at: _,
+            last_sync_slot: _,
+            last_sync_unix_ts: _,
+            seq: _,
+            // managed by the admin
+            suspension: _,
+            // synced
+            authority,
+            status,
+            block_time_ms,
+            base_fee,
+            features,
+            load_average,
+            country_code,
+            coordinates,
+            region,
+            endpoints,
+        } = RecordV1 {
+            authority: Pubkey::new_unique(),
+            status: ErStatus::Draining,
+            block_time_ms: 400,
+            base_fee: 5000,
+            features: FeaturesSet::default().activate(Feature::Randomness),
+            load_average: 42,
+            country_code: CountryCode::try_from("USA").unwrap(),
+            coordinates: Some(Coordinates::from_degrees(40.71, -74.0).unwrap()),
+            region: Some("us-east-1".to_string()),
+            endpoints: vec![
+                Endpoint::rpc("https://rpc2.er.app".to_string()),
+                Endpoint {
+                    kind: ServiceKind::Ws,
+                    priority: 0,
+                    weight: 0,
+                    addr: "wss://ws.er.app".to_string(),
+                },
+            ],
+            ..before.clone()
+        };
+        let [rpc, ws] = [endpoints[0].clone(), endpoints[1].clone()];
+
+        let ix = SyncInstruction::V1(SyncRecordV1 {
+            identity: before.identity,
+            expected_seq: None,
+            authority: Some(authority),
+            status: Some(status),
+            block_time_ms: Some(block_time_ms),
+            base_fee: Some(base_fee),
+            features: Some(features.clone()),
+            load_average: Some(load_average),
+            country_code: Some(country_code),
+            coordinates: Some(coordinates),
+            region: Some(region.clone()),
+            addr: Some(rpc.addr.clone()),
+            endpoints: vec![EndpointUpdate::Add(ws.clone())],
+        });
+        let mut record = ErRecord::V1(before.clone());
+        ix.apply(&mut record).unwrap();
+        let ErRecord::V1(after) = record else {
+            panic!("sync has changed the record version");
+        };
+
+        let expected = RecordV1 {
+            authority,
+            status,
+            block_time_ms,
+            base_fee,
+            features,
+            load_average,
+            country_code,
+            coordinates,
+            region,
+            endpoints: vec![rpc, ws],
+            ..before
+        };
+        assert_eq!(after, expected);
+    }
 }
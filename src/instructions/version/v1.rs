@@ -0,0 +1,32 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{
+    endpoint::EndpointUpdate, features::FeaturesSet, location::Coordinates, record::CountryCode,
+    status::ErStatus,
+};
+
+/// Sync instruction data, version 1, extends version 0 with compare-and-swap, authority
+/// update, geographic location and endpoint modifications
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SyncRecordV1 {
+    pub identity: Pubkey,
+    /// If set, the sync is only applied when the sequence
+    /// number of the record is equal to the given value
+    pub expected_seq: Option<u64>,
+    /// New authority of the record, can only be set by ER node identity
+    pub authority: Option<Pubkey>,
+    pub status: Option<ErStatus>,
+    pub block_time_ms: Option<u16>,
+    pub base_fee: Option<u16>,
+    pub features: Option<FeaturesSet>,
+    pub load_average: Option<u32>,
+    pub country_code: Option<CountryCode>,
+    /// If set, replaces the coordinates of the record, `Some(None)` withdraws them
+    pub coordinates: Option<Option<Coordinates>>,
+    /// If set, replaces the region of the record, `Some(None)` withdraws it
+    pub region: Option<Option<String>>,
+    pub addr: Option<String>,
+    /// Modifications of individual endpoints, applied in order
+    pub endpoints: Vec<EndpointUpdate>,
+}
@@ -2,7 +2,9 @@
 
 use solana_program::declare_id;
 
+pub mod client;
 pub mod consts;
+pub mod error;
 pub mod instructions;
 pub mod state;
 
@@ -0,0 +1,72 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use super::utils::{check_admin, create_pda_account};
+use crate::{consts::APPROVAL_SEED, error::MdpError, state::approval::Approval, ID};
+
+/// Approves ER node with the given identity to register while the program is in permissioned mode
+pub fn process_approval_grant<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    identity: Pubkey,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let approval_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    check_admin(admin, config_account)?;
+
+    let (pda, bump) = Approval::pda_for(&identity);
+    if pda != *approval_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    if *approval_account.owner == ID {
+        return Err(MdpError::AlreadyApproved.into());
+    }
+
+    let approval = Approval {
+        identity,
+        granted_by: *admin.key,
+        granted_at: Clock::get()?.unix_timestamp,
+    };
+    let data = approval.to_account_data()?;
+    let seeds: &[&[u8]] = &[APPROVAL_SEED, identity.as_ref(), &[bump]];
+    create_pda_account(&data, approval_account, admin, system_program, seeds)
+}
+
+/// Revokes the approval of ER node with the given identity, returning the rent to the admin.
+/// The record of already registered ER node is kept intact
+pub fn process_approval_revocation<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    identity: Pubkey,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let approval_account = next_account_info(&mut accounts)?;
+
+    check_admin(admin, config_account)?;
+
+    if Approval::pda_for(&identity).0 != *approval_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    if *approval_account.owner != ID || approval_account.lamports() == 0 {
+        return Err(MdpError::NotApproved.into());
+    }
+
+    let admin_balance = admin.lamports();
+    let approval_balance = approval_account.lamports();
+    **admin.try_borrow_mut_lamports()? = admin_balance
+        .checked_add(approval_balance)
+        .ok_or(MdpError::LamportsOverflow)?;
+    **approval_account.try_borrow_mut_lamports()? = 0;
+
+    approval_account.assign(&Pubkey::default());
+    approval_account.resize(0)?;
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction::transfer;
+
+use super::utils::{create_pda_account, load_config, store_account_data};
+use crate::{
+    consts::BOND_SEED,
+    error::MdpError,
+    state::{bond::Bond, record::ErRecord},
+    ID,
+};
+
+/// Adds the given amount of lamports to the bond of ER node, creating the vault if necessary.
+/// Anyone can fund the locked bond, while only ER identity can deposit into the unlocking one,
+/// which locks it again, so that the cooldown can't be restarted by a third party
+pub fn process_bond_deposit<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let funder = next_account_info(&mut accounts)?;
+    let bond_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    if !funder.is_signer {
+        return Err(MdpError::MissingSignature.into());
+    }
+    if amount == 0 {
+        return Err(MdpError::InvalidBondAmount.into());
+    }
+
+    let (pda, bump) = Bond::pda_for(&node_id);
+    if pda != *bond_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    let mut bond = if *bond_account.owner != ID {
+        let bond = Bond {
+            identity: node_id,
+            amount: 0,
+            unlock_slot: None,
+        };
+        let data = bond.to_account_data()?;
+        let seeds: &[&[u8]] = &[BOND_SEED, node_id.as_ref(), &[bump]];
+        create_pda_account(&data, bond_account, funder, system_program, seeds)?;
+        bond
+    } else {
+        Bond::try_from_account_data(&bond_account.try_borrow_data()?)?
+    };
+    if bond.unlock_slot.is_some() && *funder.key != node_id {
+        msg!("only ER node identity can deposit into the unlocking bond");
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    invoke(
+        &transfer(funder.key, bond_account.key, amount),
+        &[funder.clone(), bond_account.clone(), system_program.clone()],
+    )?;
+    bond.amount = bond
+        .amount
+        .checked_add(amount)
+        .ok_or(MdpError::LamportsOverflow)?;
+    bond.unlock_slot = None;
+    let data = bond.to_account_data()?;
+    store_account_data(&data, bond_account, funder, system_program)
+}
+
+/// Starts the cooldown of the bond, which is only possible once ER node isn't registered
+pub fn process_bond_unlock<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+) -> Result<(), ProgramError> {
+    let identity = next_account_info(&mut accounts)?;
+    let bond_account = next_account_info(&mut accounts)?;
+    let pda_account = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    let mut bond = load_bond(identity, bond_account, node_id)?;
+
+    if ErRecord::pda_for(&node_id).0 != *pda_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    // anyone can send lamports to the closed record PDA, so only the ownership counts
+    if *pda_account.owner == ID {
+        msg!("bond can't be unlocked while ER node is registered");
+        return Err(MdpError::BondLocked.into());
+    }
+
+    let cooldown = load_config(config_account)?.bond_cooldown_slots;
+    bond.unlock_slot = Some(Clock::get()?.slot.saturating_add(cooldown));
+    let data = bond.to_account_data()?;
+    store_account_data(&data, bond_account, identity, system_program)
+}
+
+/// Returns the bond along with the vault rent to ER node identity, once the cooldown has elapsed
+pub fn process_bond_withdrawal<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+) -> Result<(), ProgramError> {
+    let identity = next_account_info(&mut accounts)?;
+    let bond_account = next_account_info(&mut accounts)?;
+
+    let bond = load_bond(identity, bond_account, node_id)?;
+
+    let slot = Clock::get()?.slot;
+    if bond.unlock_slot.is_none_or(|unlock| slot < unlock) {
+        msg!("bond is locked until slot {:?}", bond.unlock_slot);
+        return Err(MdpError::BondLocked.into());
+    }
+
+    let identity_balance = identity.lamports();
+    let bond_balance = bond_account.lamports();
+    **identity.try_borrow_mut_lamports()? = identity_balance
+        .checked_add(bond_balance)
+        .ok_or(MdpError::LamportsOverflow)?;
+    **bond_account.try_borrow_mut_lamports()? = 0;
+
+    bond_account.assign(&Pubkey::default());
+    bond_account.resize(0)?;
+
+    Ok(())
+}
+
+/// Deserializes the bond of ER node, making sure that the signer is its identity
+fn load_bond(
+    identity: &AccountInfo,
+    bond_account: &AccountInfo,
+    node_id: Pubkey,
+) -> Result<Bond, ProgramError> {
+    if !identity.is_signer {
+        msg!("bond can only be managed by ER node identity");
+        return Err(MdpError::MissingSignature.into());
+    }
+    if *identity.key != node_id {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+    if Bond::pda_for(&node_id).0 != *bond_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    if *bond_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+    let data = bond_account.try_borrow_data()?;
+    Ok(Bond::try_from_account_data(&data)?)
+}
@@ -0,0 +1,125 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use solana_sdk_ids::bpf_loader_upgradeable;
+
+use super::utils::{create_pda_account, load_config, store_account_data};
+use crate::{
+    consts::CONFIG_SEED,
+    error::MdpError,
+    state::config::{ConfigUpdate, ProgramConfig},
+    ID,
+};
+
+/// Tag of the program data variant of the upgradeable loader account state
+const PROGRAM_DATA_TAG: u32 = 3;
+/// Size of the program data account metadata: tag, slot and optional upgrade authority
+const PROGRAM_DATA_METADATA_LEN: usize = 45;
+
+/// Creates the program config with the default policy, the signer becomes the admin. Only the
+/// upgrade authority of the program can do it, so the deployment can't be front-run
+pub fn process_config_init<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+    let program_data = next_account_info(&mut accounts)?;
+
+    if !admin.is_signer {
+        return Err(MdpError::MissingSignature.into());
+    }
+
+    let (pda, bump) = ProgramConfig::pda();
+    if pda != *config_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    if upgrade_authority(program_data)? != Some(*admin.key) {
+        msg!("program config can only be initialized by the program upgrade authority");
+        return Err(MdpError::AdminMismatch.into());
+    }
+
+    if *config_account.owner == ID {
+        return Err(MdpError::ConfigAlreadyInitialized.into());
+    }
+
+    let data = ProgramConfig::new(*admin.key, Clock::get()?.slot).to_account_data()?;
+    let seeds: &[&[u8]] = &[CONFIG_SEED, &[bump]];
+    create_pda_account(&data, config_account, admin, system_program, seeds)
+}
+
+/// Modifies the program policy or offers the admin role to another key
+pub fn process_config_update<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    update: ConfigUpdate,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    if !admin.is_signer {
+        return Err(MdpError::MissingSignature.into());
+    }
+
+    let mut config = load_config(config_account)?;
+    if config.admin != *admin.key {
+        msg!("program config can only be modified by its admin");
+        return Err(MdpError::AdminMismatch.into());
+    }
+
+    config
+        .apply(update)
+        .inspect_err(|e| msg!("failed to update program config: {}", e))?;
+    let data = config.to_account_data()?;
+    store_account_data(&data, config_account, admin, system_program)
+}
+
+/// Completes the admin transfer, signed by the key which has been offered the admin role
+pub fn process_admin_acceptance<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+) -> Result<(), ProgramError> {
+    let new_admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    if !new_admin.is_signer {
+        return Err(MdpError::MissingSignature.into());
+    }
+
+    let mut config = load_config(config_account)?;
+    config.accept_admin(new_admin.key)?;
+    let data = config.to_account_data()?;
+    store_account_data(&data, config_account, new_admin, system_program)
+}
+
+/// Reads the upgrade authority of the program from its program data account,
+/// None if the program has been made immutable
+fn upgrade_authority(program_data: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+    if *program_data.key != ProgramConfig::program_data_address() {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    if *program_data.owner != bpf_loader_upgradeable::ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+    let data = program_data.try_borrow_data()?;
+    let metadata = data
+        .get(..PROGRAM_DATA_METADATA_LEN)
+        .ok_or(MdpError::InvalidRecordData)?;
+    let (tag, rest) = metadata.split_at(4);
+    if tag != PROGRAM_DATA_TAG.to_le_bytes() {
+        return Err(MdpError::InvalidRecordData.into());
+    }
+    // the slot of the last deployment is skipped
+    match rest[8] {
+        0 => Ok(None),
+        _ => Pubkey::try_from(&rest[9..])
+            .map(Some)
+            .map_err(|_| MdpError::InvalidRecordData.into()),
+    }
+}
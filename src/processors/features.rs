@@ -0,0 +1,83 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    program_error::ProgramError,
+};
+
+use super::utils::{check_admin, create_pda_account, store_account_data};
+use crate::{
+    consts::FEATURE_REGISTRY_SEED,
+    error::MdpError,
+    state::registry::{FeatureDefinition, FeatureRegistry},
+    ID,
+};
+
+/// Defines new feature in the feature registry, the registry is created on the first
+/// definition. Only the program admin can define features
+pub fn process_feature_definition<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    feature: FeatureDefinition,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let registry_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    check_admin(admin, config_account)?;
+
+    let (pda, bump) = FeatureRegistry::pda();
+    if pda != *registry_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    if *registry_account.owner != ID {
+        let mut registry = FeatureRegistry::default();
+        registry
+            .define(feature)
+            .inspect_err(|e| msg!("failed to define feature: {}", e))?;
+        let data = registry.to_account_data()?;
+        let seeds: &[&[u8]] = &[FEATURE_REGISTRY_SEED, &[bump]];
+        return create_pda_account(&data, registry_account, admin, system_program, seeds);
+    }
+
+    let mut registry = load_registry(registry_account)?;
+    registry
+        .define(feature)
+        .inspect_err(|e| msg!("failed to define feature: {}", e))?;
+    let data = registry.to_account_data()?;
+    store_account_data(&data, registry_account, admin, system_program)
+}
+
+/// Marks the feature with the given bit index as deprecated in the feature registry
+pub fn process_feature_deprecation<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    bit: u8,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let registry_account = next_account_info(&mut accounts)?;
+
+    check_admin(admin, config_account)?;
+
+    if FeatureRegistry::pda().0 != *registry_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    let mut registry = load_registry(registry_account)?;
+    registry.deprecate(bit)?;
+    // deprecation doesn't change the size of the registry
+    let data = registry.to_account_data()?;
+    registry_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&data);
+    Ok(())
+}
+
+/// Deserializes the existing registry
+fn load_registry(registry_account: &AccountInfo) -> Result<FeatureRegistry, ProgramError> {
+    if *registry_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+    let data = registry_account.try_borrow_data()?;
+    Ok(FeatureRegistry::try_from_account_data(&data)?)
+}
@@ -0,0 +1,52 @@
+use solana_program::msg;
+use solana_program::pubkey::Pubkey;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    program_error::ProgramError,
+    sysvar::Sysvar,
+};
+
+use super::utils::store_record;
+use crate::error::MdpError;
+use crate::state::record::ErRecord;
+use crate::ID;
+
+/// Refreshes the liveness of the given ER node record, legacy records are migrated to the
+/// latest version along the way, the ER identity pays for the reallocation
+pub fn process_heartbeat<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+) -> Result<(), ProgramError> {
+    let signer = next_account_info(&mut accounts)?;
+    let pda_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    if *pda_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+
+    if !signer.is_signer {
+        msg!("heartbeat should be signed by ER node identity");
+        return Err(MdpError::MissingSignature.into());
+    }
+    if *signer.key != node_id {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    let data = pda_account.try_borrow_data()?;
+    let record = ErRecord::try_from_account_data(&data)?;
+    drop(data);
+
+    if node_id != *record.identity() {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+    if record.pda().0 != *pda_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    // liveness is only tracked starting from version 1 records
+    let mut record = record.into_latest();
+    record.stamp_heartbeat(&Clock::get()?);
+
+    store_record(&record, pda_account, signer, system_program)
+}
@@ -0,0 +1,65 @@
+use solana_program::msg;
+use solana_program::pubkey::Pubkey;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    program_error::ProgramError,
+    sysvar::Sysvar,
+};
+
+use super::utils::store_record;
+use crate::error::MdpError;
+use crate::state::record::ErRecord;
+use crate::ID;
+
+/// Migrates record of the given ER node to the latest record version, reallocating its PDA
+pub fn process_migration<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+) -> Result<(), ProgramError> {
+    let payer = next_account_info(&mut accounts)?;
+    let pda_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    if *pda_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+
+    if !payer.is_signer {
+        msg!("transaction payer should be signer");
+        return Err(MdpError::MissingSignature.into());
+    }
+    if *payer.key != node_id {
+        msg!("transaction payer should be the same as ER node identity");
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    if pda_account.lamports() == 0 {
+        return Err(MdpError::RecordNotFound.into());
+    }
+    let data = pda_account.try_borrow_data()?;
+    let record = ErRecord::try_from_account_data(&data)?;
+    drop(data);
+
+    if node_id != *record.identity() {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    let (pda, _) = record.pda();
+
+    if pda != *pda_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    if record.is_latest() {
+        msg!("record is already of the latest version");
+        return Ok(());
+    }
+
+    let mut record = record.into_latest();
+    // migration is performed by the operator, so the node is evidently alive,
+    // without it the record with unknown liveness could be reaped right away
+    record.stamp_heartbeat(&Clock::get()?);
+
+    store_record(&record, pda_account, payer, system_program)
+}
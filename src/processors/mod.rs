@@ -1,3 +1,13 @@
+pub mod approval;
+pub mod bond;
+pub mod config;
+pub mod features;
+pub mod heartbeat;
+pub mod migrate;
+pub mod reap;
 pub mod register;
+pub mod rotate;
+pub mod suspension;
 pub mod sync;
 pub mod unregister;
+mod utils;
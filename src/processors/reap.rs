@@ -0,0 +1,95 @@
+use solana_program::msg;
+use solana_program::pubkey::Pubkey;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    program_error::ProgramError,
+    sysvar::Sysvar,
+};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+use super::utils::load_config;
+use crate::error::MdpError;
+use crate::state::record::ErRecord;
+use crate::ID;
+
+/// Removes the record of ER node, which has been stale for longer than the grace period.
+/// Anyone can reap such a record, the caller gets a bounty, the rest of rent goes to ER identity.
+/// The liveness period and the bounty follow the program config. Legacy (V0) records, which
+/// predate the liveness tracking and haven't been migrated since, are considered last seen at
+/// the config initialization, so their operators get the whole grace period to migrate them.
+/// Suspended records can't be reaped, otherwise the suspension could be shed by re-registration
+pub fn process_reap<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+) -> Result<(), ProgramError> {
+    let caller = next_account_info(&mut accounts)?;
+    let pda_account = next_account_info(&mut accounts)?;
+    let identity = next_account_info(&mut accounts)?;
+    let config = load_config(next_account_info(&mut accounts)?)?;
+
+    if *pda_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+
+    if !caller.is_signer {
+        msg!("reap caller should be signer");
+        return Err(MdpError::MissingSignature.into());
+    }
+    if *identity.key != node_id {
+        msg!("rent recipient should be the ER node identity");
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    if pda_account.lamports() == 0 {
+        return Err(MdpError::RecordNotFound.into());
+    }
+    let data = pda_account.try_borrow_data()?;
+    let record = ErRecord::try_from_account_data(&data)?;
+    drop(data);
+
+    if node_id != *record.identity() {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+    if record.pda().0 != *pda_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    let clock = Clock::get()?;
+    if record.is_suspended(clock.unix_timestamp) {
+        msg!("ER node is suspended, its record can't be reaped");
+        return Err(MdpError::Suspended.into());
+    }
+
+    let ttl = config
+        .record_ttl_slots
+        .saturating_add(config.reap_grace_slots);
+    let live = match record {
+        ErRecord::V0(_) => config
+            .initialized_slot
+            .is_none_or(|slot| clock.slot.saturating_sub(slot) <= ttl),
+        ErRecord::V1(_) => record.is_live(clock.slot, ttl),
+    };
+    if live {
+        msg!("record was last seen at slot {}", record.last_sync_slot());
+        return Err(MdpError::RecordNotExpired.into());
+    }
+
+    let pda_balance = pda_account.lamports();
+    let bounty = config.reap_bounty(pda_balance);
+    // the balances are read right before crediting, as the caller can be the ER identity itself
+    let caller_balance = caller.lamports();
+    **caller.try_borrow_mut_lamports()? = caller_balance
+        .checked_add(bounty)
+        .ok_or(MdpError::LamportsOverflow)?;
+    let identity_balance = identity.lamports();
+    **identity.try_borrow_mut_lamports()? = identity_balance
+        .checked_add(pda_balance - bounty)
+        .ok_or(MdpError::LamportsOverflow)?;
+    **pda_account.try_borrow_mut_lamports()? = 0;
+
+    pda_account.assign(&SYSTEM_PROGRAM_ID);
+    pda_account.resize(0)?;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
-use borsh::BorshSerialize;
 use solana_program::msg;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     program::invoke_signed,
     program_error::ProgramError,
     rent::Rent,
@@ -9,29 +9,52 @@ use solana_program::{
 };
 use solana_system_interface::instruction::create_account;
 
-use crate::{state::record::ErRecord, ID};
+use super::utils::{check_admission, load_config, next_feature_registry, next_payer};
+use crate::{error::MdpError, state::record::ErRecord, ID};
 
 /// Registers ER node in domain registry, by creating a record (PDA) with all the relevant ER information
 pub fn process_registration<'a>(
-    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
     record: ErRecord,
 ) -> Result<(), ProgramError> {
-    let payer = next_account_info(&mut accounts)?;
+    let mut accounts = accounts.peekable();
+    let signer = next_account_info(&mut accounts)?;
     let pda_account = next_account_info(&mut accounts)?;
     let system_program = next_account_info(&mut accounts)?;
+    let payer = next_payer(&mut accounts, signer)?;
+    // the config is required even before it's initialized, otherwise the policy could be
+    // bypassed by leaving it out
+    let config = load_config(next_account_info(&mut accounts)?)?;
 
-    if !payer.is_signer {
-        msg!("transaction payer should be signer");
-        return Err(ProgramError::MissingRequiredSignature);
+    if !signer.is_signer {
+        msg!("ER node identity should be signer");
+        return Err(MdpError::MissingSignature.into());
     }
 
-    if payer.key != record.identity() {
-        msg!("transaction payer should be the same as ER node identity");
-        return Err(ProgramError::InvalidArgument);
+    if signer.key != record.identity() {
+        msg!("transaction signer should be the same as ER node identity");
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    check_admission(&mut accounts, &config, signer.key)?;
+    let registry = next_feature_registry(&mut accounts, &config)?;
+
+    // records are always stored in the latest version, older ones lack the liveness
+    // tracking, which would make them exempt from reaping
+    let mut record = record.into_latest();
+
+    record
+        .validate()
+        .inspect_err(|e| msg!("invalid ER record: {}", e))?;
+
+    if let Some(registry) = registry {
+        registry
+            .validate_features(record.features())
+            .inspect_err(|e| msg!("invalid ER record features: {}", e))?;
     }
 
     if pda_account.lamports() != 0 {
-        return Err(ProgramError::AccountAlreadyInitialized);
+        return Err(MdpError::RecordAlreadyExists.into());
     }
 
     let (pda, bump) = record.pda();
@@ -42,11 +65,11 @@ pub fn process_registration<'a>(
             pda,
             pda_account.key
         );
-        return Err(ProgramError::InvalidArgument);
+        return Err(MdpError::PdaMismatch.into());
     }
 
-    let mut data = Vec::new();
-    record.serialize(&mut data)?;
+    record.stamp_registration(&Clock::get()?);
+    let data = record.to_account_data()?;
 
     let space = data.len();
     let rent = Rent::get()?.minimum_balance(data.len());
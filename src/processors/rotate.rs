@@ -0,0 +1,109 @@
+use solana_program::msg;
+use solana_program::pubkey::Pubkey;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    program::invoke_signed,
+    program_error::ProgramError,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction::create_account;
+
+use super::utils::{check_admission, load_config};
+use crate::error::MdpError;
+use crate::state::record::ErRecord;
+use crate::ID;
+
+/// Moves the record of the given ER node to the PDA derived from its new identity,
+/// the new record is paid for by the old identity, which then receives the old record's rent.
+/// The new identity has to be admitted the same way as on registration (approval and bond)
+pub fn process_identity_rotation<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+    new_node_id: Pubkey,
+) -> Result<(), ProgramError> {
+    let payer = next_account_info(&mut accounts)?;
+    let new_identity = next_account_info(&mut accounts)?;
+    let pda_account = next_account_info(&mut accounts)?;
+    let new_pda_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+    let config = load_config(next_account_info(&mut accounts)?)?;
+
+    if *pda_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+
+    if !payer.is_signer || !new_identity.is_signer {
+        msg!("identity rotation should be signed by both old and new ER node identities");
+        return Err(MdpError::MissingSignature.into());
+    }
+    if *payer.key != node_id || *new_identity.key != new_node_id {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    if pda_account.lamports() == 0 {
+        return Err(MdpError::RecordNotFound.into());
+    }
+    let data = pda_account.try_borrow_data()?;
+    let record = ErRecord::try_from_account_data(&data)?;
+    drop(data);
+
+    if node_id != *record.identity() {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+    if record.pda().0 != *pda_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    if new_pda_account.lamports() != 0 {
+        return Err(MdpError::RecordAlreadyExists.into());
+    }
+    // otherwise rotation could be used to bypass approval or bond requirements
+    check_admission(&mut accounts, &config, &new_node_id)?;
+
+    let mut record = record.rotate_identity(new_node_id);
+    let (new_pda, bump) = record.pda();
+    if new_pda != *new_pda_account.key {
+        msg!(
+            "pubkey for rotated record pda doesn't match provided one {} != {}",
+            new_pda,
+            new_pda_account.key
+        );
+        return Err(MdpError::PdaMismatch.into());
+    }
+    // rotation is signed by the node, so it's evidently alive, this also
+    // prevents reaping of the rotated legacy records with unknown liveness
+    record.stamp_heartbeat(&Clock::get()?);
+    let data = record.to_account_data()?;
+
+    let space = data.len();
+    let rent = Rent::get()?.minimum_balance(space);
+    let [s1, s2] = record.seeds();
+
+    let create_pda_ix = create_account(payer.key, &new_pda, rent, space as u64, &ID);
+    invoke_signed(
+        &create_pda_ix,
+        &[
+            payer.clone(),
+            new_pda_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[s1, s2, &[bump]]],
+    )?;
+    new_pda_account
+        .try_borrow_mut_data()?
+        .copy_from_slice(&data);
+
+    let payer_balance = payer.lamports();
+    let pda_balance = pda_account.lamports();
+    **payer.try_borrow_mut_lamports()? = payer_balance
+        .checked_add(pda_balance)
+        .ok_or(MdpError::LamportsOverflow)?;
+    **pda_account.try_borrow_mut_lamports()? = 0;
+
+    pda_account.assign(system_program.key);
+    pda_account.resize(0)?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::utils::{check_admin, store_record};
+use crate::{
+    error::MdpError,
+    state::{record::ErRecord, status::Suspension},
+    ID,
+};
+
+/// Imposes (or lifts, if `suspension` is None) the suspension of the given ER node, the record
+/// is migrated to the latest version, with the possible rent top-up paid by the admin
+pub fn process_suspension<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    node_id: Pubkey,
+    suspension: Option<Suspension>,
+) -> Result<(), ProgramError> {
+    let admin = next_account_info(&mut accounts)?;
+    let config_account = next_account_info(&mut accounts)?;
+    let pda_account = next_account_info(&mut accounts)?;
+    let system_program = next_account_info(&mut accounts)?;
+
+    check_admin(admin, config_account)?;
+
+    if *pda_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+    if pda_account.lamports() == 0 {
+        return Err(MdpError::RecordNotFound.into());
+    }
+    let data = pda_account.try_borrow_data()?;
+    let record = ErRecord::try_from_account_data(&data)?;
+    drop(data);
+
+    if node_id != *record.identity() {
+        return Err(MdpError::IdentityMismatch.into());
+    }
+    if record.pda().0 != *pda_account.key {
+        return Err(MdpError::PdaMismatch.into());
+    }
+
+    let mut record = record.into_latest();
+    record.set_suspension(suspension)?;
+
+    store_record(&record, pda_account, admin, system_program)
+}
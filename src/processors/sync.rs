@@ -1,84 +1,82 @@
-use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    program::invoke,
+    clock::Clock,
+    msg,
     program_error::ProgramError,
-    rent::Rent,
     sysvar::Sysvar,
 };
-use solana_system_interface::instruction::transfer;
 
-use crate::{instructions::sync::SyncInstruction, state::record::ErRecord, ID};
+use super::utils::{load_config, next_feature_registry, next_payer, store_record};
+use crate::{error::MdpError, instructions::sync::SyncInstruction, state::record::ErRecord, ID};
 
 /// Synchronize updated ER information with existing domain registry record
 pub fn process_sync_record<'a>(
-    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
-    mut ix: SyncInstruction,
+    accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    ix: SyncInstruction,
 ) -> Result<(), ProgramError> {
-    let payer = next_account_info(&mut accounts)?;
+    let mut accounts = accounts.peekable();
+    let signer = next_account_info(&mut accounts)?;
     let pda_account = next_account_info(&mut accounts)?;
     let system_program = next_account_info(&mut accounts)?;
+    let payer = next_payer(&mut accounts, signer)?;
+    let config = load_config(next_account_info(&mut accounts)?)?;
+    let registry = next_feature_registry(&mut accounts, &config)?;
 
     if *pda_account.owner != ID {
-        return Err(ProgramError::InvalidAccountOwner);
+        return Err(MdpError::InvalidRecordOwner.into());
     }
 
-    if !(payer.is_signer && payer.key == ix.identity()) {
-        return Err(ProgramError::InvalidArgument);
+    if !signer.is_signer {
+        return Err(MdpError::MissingSignature.into());
     }
 
     if pda_account.lamports() == 0 {
-        return Err(ProgramError::UninitializedAccount);
+        return Err(MdpError::RecordNotFound.into());
     }
 
     let pda = ix.pda();
 
     if pda != *pda_account.key {
-        return Err(ProgramError::InvalidArgument);
+        return Err(MdpError::PdaMismatch.into());
     }
 
-    let mut data = pda_account.try_borrow_mut_data()?;
-    let mut record =
-        ErRecord::try_from_slice(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    let data = pda_account.try_borrow_data()?;
+    let record = ErRecord::try_from_account_data(&data)?;
+    drop(data);
 
-    if let Some(addr) = ix.addr().take() {
-        record.set_addr(addr);
-    }
-    if let Some(base_fee) = ix.base_fee().take() {
-        record.set_base_fee(base_fee);
+    if !record.is_authorized(signer.key) {
+        msg!("transaction signer should be either ER node identity or record authority");
+        return Err(MdpError::IdentityMismatch.into());
     }
-    if let Some(block_time_ms) = ix.block_time_ms().take() {
-        record.set_block_time_ms(block_time_ms);
-    }
-    if let Some(features) = ix.features().take() {
-        record.set_features(features);
+
+    // legacy records are upgraded, so that the sync gets stamped and the new fields can be set
+    let mut record = record.into_latest();
+
+    if let Some(expected) = ix.expected_seq() {
+        if record.seq() != expected {
+            msg!("record sequence number {} != {}", record.seq(), expected);
+            return Err(MdpError::SeqMismatch.into());
+        }
     }
-    if let Some(status) = ix.status().take() {
-        record.set_status(status);
+
+    if ix.authority().is_some() && signer.key != record.identity() {
+        msg!("record authority can only be changed by ER node identity");
+        return Err(MdpError::IdentityMismatch.into());
     }
-    if let Some(load_average) = ix.load_average().take() {
-        record.set_load_average(load_average);
+
+    ix.apply(&mut record)
+        .inspect_err(|e| msg!("failed to apply sync to the record: {}", e))?;
+    if let Some(registry) = registry {
+        registry
+            .validate_features(record.features())
+            .inspect_err(|e| msg!("invalid ER record features: {}", e))?;
     }
-    let new_size = borsh::object_length(&record)?;
-    let old_size = data.len();
-    if old_size == new_size {
-        return Ok(record.serialize(&mut *data)?);
-    };
-    drop(data);
-    let rent_new = Rent::get()?.minimum_balance(new_size);
-    let rent_old = Rent::get()?.minimum_balance(old_size);
-    if rent_new > rent_old {
-        invoke(
-            &transfer(payer.key, pda_account.key, rent_new - rent_old),
-            &[payer.clone(), pda_account.clone(), system_program.clone()],
-        )?;
-    } else {
-        **pda_account.try_borrow_mut_lamports()? -= rent_old - rent_new;
-        **payer.try_borrow_mut_lamports()? += rent_old - rent_new;
+    let clock = Clock::get()?;
+    // expired suspension is lifted on the next sync, the active one is kept intact
+    if record.suspension().is_some() && !record.is_suspended(clock.unix_timestamp) {
+        record.set_suspension(None)?;
     }
-    pda_account.resize(new_size)?;
-    data = pda_account.try_borrow_mut_data()?;
-    record.serialize(&mut *data)?;
+    record.stamp_sync(&clock);
 
-    Ok(())
+    store_record(&record, pda_account, payer, system_program)
 }
@@ -1,67 +1,90 @@
-use borsh::BorshDeserialize;
 use solana_program::msg;
 use solana_program::pubkey::Pubkey;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     program_error::ProgramError,
+    sysvar::Sysvar,
 };
 
+use super::utils::next_payer;
+use crate::error::MdpError;
 use crate::state::record::ErRecord;
 use crate::ID;
 
-/// Unregisters given ER node, by removing its record from domain registry
+/// Unregisters given ER node, by removing its record from domain registry, the rent
+/// is reclaimed by the recipient, if specified, or by the payer of the transaction
 pub fn process_unregistration<'a>(
-    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
     node_id: Pubkey,
+    recipient: Option<Pubkey>,
 ) -> Result<(), ProgramError> {
-    let payer = next_account_info(&mut accounts)?;
+    let mut accounts = accounts.peekable();
+    let signer = next_account_info(&mut accounts)?;
     let pda_account = next_account_info(&mut accounts)?;
     let system_program = next_account_info(&mut accounts)?;
+    let recipient_account = recipient
+        .map(|_| next_account_info(&mut accounts))
+        .transpose()?;
+    let payer = next_payer(&mut accounts, signer)?;
 
     if *pda_account.owner != ID {
-        return Err(ProgramError::InvalidAccountOwner);
+        return Err(MdpError::InvalidRecordOwner.into());
     }
 
-    if !payer.is_signer {
-        msg!("transaction payer should be signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-    if *payer.key != node_id {
-        msg!("transaction payer should be the same as ER node identity");
-        return Err(ProgramError::InvalidArgument);
+    if !signer.is_signer {
+        msg!("ER node identity or record authority should be signer");
+        return Err(MdpError::MissingSignature.into());
     }
 
     if pda_account.lamports() == 0 {
         msg!("tried to unregister non-exsistent record PDA");
-        return Err(ProgramError::UninitializedAccount);
+        return Err(MdpError::RecordNotFound.into());
     }
     let data = pda_account.try_borrow_data()?;
-    let record = ErRecord::try_from_slice(&data).map_err(|e| {
+    let record = ErRecord::try_from_account_data(&data).inspect_err(|_| {
         msg!(
-            "failed to deserialize record entry from slice for account {}: {}",
-            pda_account.key,
-            e
+            "failed to deserialize record entry from slice for account {}",
+            pda_account.key
         );
-        ProgramError::InvalidAccountData
     })?;
 
     drop(data);
 
     if node_id != *record.identity() {
-        return Err(ProgramError::InvalidArgument);
+        return Err(MdpError::IdentityMismatch.into());
+    }
+    if !record.is_authorized(signer.key) {
+        msg!("transaction signer should be either ER node identity or record authority");
+        return Err(MdpError::IdentityMismatch.into());
+    }
+
+    // otherwise the operator could escape the suspension by registering anew
+    if record.is_suspended(Clock::get()?.unix_timestamp) {
+        msg!("suspended ER node can't be unregistered");
+        return Err(MdpError::Suspended.into());
     }
 
     let (pda, _) = record.pda();
 
     if pda != *pda_account.key {
-        return Err(ProgramError::InvalidArgument);
+        return Err(MdpError::PdaMismatch.into());
     }
 
-    let payer_balance = payer.lamports();
+    let recipient = match (recipient_account, recipient) {
+        (Some(account), Some(key)) if *account.key != key => {
+            msg!("recipient account {} != {}", account.key, key);
+            return Err(MdpError::RecipientMismatch.into());
+        }
+        (Some(account), _) => account,
+        (None, _) => payer,
+    };
+
+    let recipient_balance = recipient.lamports();
     let pda_balance = pda_account.lamports();
-    **payer.try_borrow_mut_lamports()? = payer_balance
+    **recipient.try_borrow_mut_lamports()? = recipient_balance
         .checked_add(pda_balance)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(MdpError::LamportsOverflow)?;
     **pda_account.try_borrow_mut_lamports()? = 0;
 
     pda_account.assign(system_program.key);
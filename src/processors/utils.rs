@@ -0,0 +1,201 @@
+use std::iter::Peekable;
+
+use solana_program::{
+    account_info::AccountInfo,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction::{allocate, assign, create_account, transfer};
+
+use crate::{
+    error::MdpError,
+    state::{
+        approval::Approval, bond::Bond, config::ProgramConfig, record::ErRecord,
+        registry::FeatureRegistry,
+    },
+    ID,
+};
+
+/// Returns the optional separate payer of the rent, which follows the required accounts
+/// of the instruction, falling back to the signer of the instruction if it's absent.
+/// The payer is followed by the program config, which is never taken for it
+pub fn next_payer<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    accounts: &mut Peekable<I>,
+    signer: &'a AccountInfo<'b>,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    let config = ProgramConfig::pda().0;
+    let Some(payer) = accounts.next_if(|account| *account.key != config) else {
+        return Ok(signer);
+    };
+    if !payer.is_signer {
+        msg!("separate rent payer should be signer");
+        return Err(MdpError::MissingSignature.into());
+    }
+    Ok(payer)
+}
+
+/// Returns the feature registry, which is the last optional account of registration and sync,
+/// if the policy requires the features of the record to be defined in it, otherwise the
+/// account is ignored
+pub fn next_feature_registry<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    config: &ProgramConfig,
+) -> Result<Option<FeatureRegistry>, ProgramError> {
+    let registry_account = accounts.next();
+    if !config.require_defined_features {
+        return Ok(None);
+    }
+    let registry_account = registry_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if *registry_account.key != FeatureRegistry::pda().0 {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    if *registry_account.owner != ID {
+        return Err(MdpError::InvalidRecordOwner.into());
+    }
+    let data = registry_account.try_borrow_data()?;
+    Ok(Some(FeatureRegistry::try_from_account_data(&data)?))
+}
+
+/// Reads the program config from the given account, falling back
+/// to the default policy if the config hasn't been initialized yet
+pub fn load_config(config_account: &AccountInfo) -> Result<ProgramConfig, ProgramError> {
+    if *config_account.key != ProgramConfig::pda().0 {
+        return Err(MdpError::PdaMismatch.into());
+    }
+    if *config_account.owner != ID {
+        return Ok(ProgramConfig::default());
+    }
+    let data = config_account.try_borrow_data()?;
+    Ok(ProgramConfig::try_from_account_data(&data)?)
+}
+
+/// Makes sure that the signer is the program admin, according to the given config account
+pub fn check_admin(admin: &AccountInfo, config_account: &AccountInfo) -> Result<(), ProgramError> {
+    if !admin.is_signer {
+        return Err(MdpError::MissingSignature.into());
+    }
+    if load_config(config_account)?.admin != *admin.key {
+        msg!("transaction signer should be the program admin");
+        return Err(MdpError::AdminMismatch.into());
+    }
+    Ok(())
+}
+
+/// Makes sure that ER node with the given identity is allowed to register under the given
+/// policy, i.e. it has been approved by the admin, if the program is in permissioned mode, and
+/// its operator has bonded at least the minimum amount of lamports, if the bond is required.
+/// The approval and the bond accounts follow the program config, each of them is only
+/// required if the policy calls for it
+pub fn check_admission<'a>(
+    mut accounts: impl Iterator<Item = &'a AccountInfo<'a>>,
+    config: &ProgramConfig,
+    identity: &Pubkey,
+) -> Result<(), ProgramError> {
+    let approval_account = accounts.next();
+    let bond_account = accounts.next();
+    if config.permissioned {
+        let approval_account = approval_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if *approval_account.key != Approval::pda_for(identity).0 {
+            return Err(MdpError::PdaMismatch.into());
+        }
+        if *approval_account.owner != ID || approval_account.lamports() == 0 {
+            msg!("ER node {} hasn't been approved to register", identity);
+            return Err(MdpError::NotApproved.into());
+        }
+    }
+    if config.min_bond_lamports > 0 {
+        let bond_account = bond_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if *bond_account.key != Bond::pda_for(identity).0 {
+            return Err(MdpError::PdaMismatch.into());
+        }
+        let bonded = if *bond_account.owner == ID {
+            Bond::try_from_account_data(&bond_account.try_borrow_data()?)?.bonded()
+        } else {
+            0
+        };
+        if bonded < config.min_bond_lamports {
+            msg!("bond {} < {} lamports", bonded, config.min_bond_lamports);
+            return Err(MdpError::InsufficientBond.into());
+        }
+    }
+    Ok(())
+}
+
+/// Creates the program owned PDA, paid for by the payer, and writes the data into it. Anyone
+/// can transfer lamports to the PDA beforehand, which would make `create_account` fail, so
+/// for such a PDA only the rent shortfall is paid, before it's allocated and assigned
+pub fn create_pda_account<'a>(
+    data: &[u8],
+    pda_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let rent = Rent::get()?.minimum_balance(data.len());
+    let space = data.len() as u64;
+    let balance = pda_account.lamports();
+    if balance == 0 {
+        let ix = create_account(payer.key, pda_account.key, rent, space, &ID);
+        invoke_signed(
+            &ix,
+            &[payer.clone(), pda_account.clone(), system_program.clone()],
+            &[seeds],
+        )?;
+    } else {
+        if rent > balance {
+            invoke(
+                &transfer(payer.key, pda_account.key, rent - balance),
+                &[payer.clone(), pda_account.clone(), system_program.clone()],
+            )?;
+        }
+        let accounts = [pda_account.clone(), system_program.clone()];
+        invoke_signed(&allocate(pda_account.key, space), &accounts, &[seeds])?;
+        invoke_signed(&assign(pda_account.key, &ID), &accounts, &[seeds])?;
+    }
+    pda_account.try_borrow_mut_data()?.copy_from_slice(data);
+    Ok(())
+}
+
+/// Writes the record into its PDA, resizing the account if necessary. The rent
+/// difference is either topped up by the payer or refunded back to it.
+pub fn store_record<'a>(
+    record: &ErRecord,
+    pda_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<(), ProgramError> {
+    // records without discriminator get upgraded to the current layout here
+    let data = record.to_account_data()?;
+    store_account_data(&data, pda_account, payer, system_program)
+}
+
+/// Same as [`store_record`], but for the already serialized account data
+pub fn store_account_data<'a>(
+    data: &[u8],
+    pda_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<(), ProgramError> {
+    let new_size = data.len();
+    let old_size = pda_account.data_len();
+    if old_size != new_size {
+        let rent_new = Rent::get()?.minimum_balance(new_size);
+        let rent_old = Rent::get()?.minimum_balance(old_size);
+        if rent_new > rent_old {
+            invoke(
+                &transfer(payer.key, pda_account.key, rent_new - rent_old),
+                &[payer.clone(), pda_account.clone(), system_program.clone()],
+            )?;
+        } else {
+            **pda_account.try_borrow_mut_lamports()? -= rent_old - rent_new;
+            **payer.try_borrow_mut_lamports()? += rent_old - rent_new;
+        }
+        pda_account.resize(new_size)?;
+    }
+    pda_account.try_borrow_mut_data()?.copy_from_slice(data);
+    Ok(())
+}
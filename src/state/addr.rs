@@ -0,0 +1,136 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::{
+    consts::{ALLOWED_ADDR_SCHEMES, MAX_ADDR_LEN},
+    error::MdpError,
+};
+
+/// Maximum length of a fully qualified domain name
+const MAX_FQDN_LEN: usize = 253;
+/// Maximum length of a single label of the domain name
+const MAX_LABEL_LEN: usize = 63;
+
+/// Validates ER node address, which should be an URL of the form
+/// `scheme://host[:port][/path]`, where scheme is one of [`ALLOWED_ADDR_SCHEMES`],
+/// host is either FQDN or IP literal (IPv6 in square brackets), and the port is non zero
+pub fn validate_addr(addr: &str) -> Result<(), MdpError> {
+    validate_url(addr, ALLOWED_ADDR_SCHEMES)
+}
+
+/// Same as [`validate_addr`], but the scheme should be one of the given ones
+pub fn validate_url(addr: &str, schemes: &[&str]) -> Result<(), MdpError> {
+    if addr.is_empty() || addr.len() > MAX_ADDR_LEN {
+        return Err(MdpError::InvalidAddr);
+    }
+    let (scheme, rest) = addr.split_once("://").ok_or(MdpError::InvalidAddr)?;
+    if !schemes.contains(&scheme) {
+        return Err(MdpError::InvalidAddr);
+    }
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    if !path.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(MdpError::InvalidAddr);
+    }
+
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        let (ip, port) = rest.split_once(']').ok_or(MdpError::InvalidAddr)?;
+        ip.parse::<Ipv6Addr>().map_err(|_| MdpError::InvalidAddr)?;
+        (None, port)
+    } else {
+        match authority.find(':') {
+            Some(i) => {
+                let (host, port) = authority.split_at(i);
+                (Some(host), port)
+            }
+            None => (Some(authority), ""),
+        }
+    };
+    if let Some(host) = host {
+        if host.parse::<Ipv4Addr>().is_err() && !is_fqdn(host) {
+            return Err(MdpError::InvalidAddr);
+        }
+    }
+    if !port.is_empty() {
+        let port = port.strip_prefix(':').ok_or(MdpError::InvalidAddr)?;
+        if !port.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(MdpError::InvalidAddr);
+        }
+        match port.parse::<u16>() {
+            Ok(p) if p != 0 => (),
+            _ => return Err(MdpError::InvalidAddr),
+        }
+    }
+    Ok(())
+}
+
+/// Checks whether the host is a valid domain name, top level
+/// domain cannot be numeric, so malformed IPv4 addresses are rejected
+fn is_fqdn(host: &str) -> bool {
+    let host = host.strip_suffix('.').unwrap_or(host);
+    if host.is_empty() || host.len() > MAX_FQDN_LEN {
+        return false;
+    }
+    let valid_labels = host.split('.').all(|label| {
+        !label.is_empty()
+            && label.len() <= MAX_LABEL_LEN
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    });
+    let numeric_tld = host
+        .rsplit('.')
+        .next()
+        .is_some_and(|tld| tld.bytes().all(|b| b.is_ascii_digit()));
+    valid_labels && !numeric_tld
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_addrs() {
+        for addr in [
+            "https://241.132.2.41:9324/",
+            "https://127.145.24.55:9324",
+            "wss://very-long-url.address.com:42425",
+            "https://er.magicblock.app",
+            "https://localhost:8899/rpc",
+            "wss://[::1]:8900",
+            "https://[2001:db8::1]",
+        ] {
+            assert_eq!(validate_addr(addr), Ok(()), "{addr}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_addrs() {
+        let long = format!("https://{}.com", "a".repeat(MAX_ADDR_LEN));
+        for addr in [
+            "",
+            " https://er.magicblock.app",
+            "https://er.magicblock.app ",
+            "https//er.magicblock.app",
+            "ftp://er.magicblock.app",
+            "https://",
+            "https://er..magicblock.app",
+            "https://-er.magicblock.app",
+            "https://er_node.magicblock.app",
+            "https://241.132.2.412:9324",
+            "https://er.magicblock.app:0",
+            "https://er.magicblock.app:65536",
+            "https://er.magicblock.app:",
+            "https://er.magicblock.app:+80",
+            "https://[::1",
+            "https://[::1]80",
+            "https://er.magicblock.app/a b",
+            &long,
+        ] {
+            assert_eq!(validate_addr(addr), Err(MdpError::InvalidAddr), "{addr}");
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    consts::{APPROVAL_DISCRIMINATOR, APPROVAL_SEED},
+    error::MdpError,
+    ID,
+};
+
+/// Admin's permission for the ER node to register, required while the program is in permissioned mode
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq)]
+pub struct Approval {
+    /// identity of the approved ER node
+    pub identity: Pubkey,
+    /// admin, who has granted the approval
+    pub granted_by: Pubkey,
+    /// unix timestamp of the approval
+    pub granted_at: i64,
+}
+
+impl Approval {
+    /// Computes approval's PDA for the ER node with the given identity
+    pub fn pda_for(identity: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[APPROVAL_SEED, identity.as_ref()], &ID)
+    }
+
+    /// Deserializes the approval from account data
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, MdpError> {
+        data.strip_prefix(APPROVAL_DISCRIMINATOR.as_slice())
+            .and_then(|data| Self::try_from_slice(data).ok())
+            .ok_or(MdpError::InvalidRecordData)
+    }
+
+    /// Serializes the approval into account data, prefixed with discriminator
+    pub fn to_account_data(&self) -> borsh::io::Result<Vec<u8>> {
+        let mut data = APPROVAL_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
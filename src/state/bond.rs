@@ -0,0 +1,108 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use super::record::ErRecord;
+use crate::{
+    consts::{BOND_DISCRIMINATOR, BOND_SEED},
+    error::MdpError,
+    ID,
+};
+
+/// Lamports locked by ER node operator in the vault PDA as a stake, which may be required for
+/// registration and can be used by the clients as a trust signal. The vault holds the rent on
+/// top of the bonded amount
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq)]
+pub struct Bond {
+    /// identity of ER node, which the bond belongs to
+    pub identity: Pubkey,
+    /// bonded amount of lamports
+    pub amount: u64,
+    /// slot, starting from which the bond can be withdrawn, None while the bond is locked
+    pub unlock_slot: Option<u64>,
+}
+
+impl Bond {
+    /// Computes bond vault's PDA for the ER node with the given identity
+    pub fn pda_for(identity: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[BOND_SEED, identity.as_ref()], &ID)
+    }
+
+    /// Deserializes the bond from account data
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, MdpError> {
+        data.strip_prefix(BOND_DISCRIMINATOR.as_slice())
+            .and_then(|data| Self::try_from_slice(data).ok())
+            .ok_or(MdpError::InvalidRecordData)
+    }
+
+    /// Serializes the bond into account data, prefixed with discriminator
+    pub fn to_account_data(&self) -> borsh::io::Result<Vec<u8>> {
+        let mut data = BOND_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Returns the amount of lamports, which backs ER node, the unlocking bond doesn't count
+    pub fn bonded(&self) -> u64 {
+        if self.unlock_slot.is_some() {
+            0
+        } else {
+            self.amount
+        }
+    }
+}
+
+/// Sorts the records by the amount bonded by their operators, largest bonds first, records
+/// without bond come last, records with equal bonds keep their order
+pub fn rank_by_bond<'a>(
+    records: impl IntoIterator<Item = (&'a ErRecord, Option<&'a Bond>)>,
+) -> Vec<(&'a ErRecord, u64)> {
+    let mut ranked: Vec<_> = records
+        .into_iter()
+        .map(|(record, bond)| (record, bond.map_or(0, Bond::bonded)))
+        .collect();
+    ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{features::FeaturesSet, fixtures};
+
+    fn record() -> ErRecord {
+        fixtures::record(FeaturesSet::default())
+    }
+
+    fn bond(record: &ErRecord, amount: u64, unlock_slot: Option<u64>) -> Bond {
+        Bond {
+            identity: *record.identity(),
+            amount,
+            unlock_slot,
+        }
+    }
+
+    #[test]
+    fn test_rank_by_bond() {
+        let records = [record(), record(), record(), record()];
+        let bonds = [
+            bond(&records[0], 100, None),
+            bond(&records[1], 1_000, Some(42)),
+            bond(&records[3], 500, None),
+        ];
+        let ranked: Vec<_> = rank_by_bond([
+            (&records[0], Some(&bonds[0])),
+            (&records[1], Some(&bonds[1])),
+            (&records[2], None),
+            (&records[3], Some(&bonds[2])),
+        ])
+        .into_iter()
+        .map(|(r, bonded)| (*r.identity(), bonded))
+        .collect();
+        let expected = [(3, 500), (0, 100), (1, 0), (2, 0)]
+            .map(|(i, bonded)| (*records[i].identity(), bonded));
+        assert_eq!(ranked, expected);
+
+        let data = bonds[1].to_account_data().unwrap();
+        assert_eq!(Bond::try_from_account_data(&data), Ok(bonds[1].clone()));
+    }
+}
@@ -0,0 +1,231 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use solana_sdk_ids::bpf_loader_upgradeable;
+
+use crate::{
+    consts::{
+        CONFIG_DISCRIMINATOR, CONFIG_SEED, DEFAULT_BOND_COOLDOWN_SLOTS, DEFAULT_REAP_BOUNTY_BPS,
+        DEFAULT_REAP_GRACE_SLOTS, DEFAULT_RECORD_TTL_SLOTS,
+    },
+    error::MdpError,
+    ID,
+};
+
+/// Number of basis points in the whole
+const MAX_BPS: u16 = 10_000;
+
+/// Singleton account holding the program wide policy, along with the admin allowed to change it.
+/// Until the config is initialized, the program follows the defaults from `consts`
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq)]
+pub struct ProgramConfig {
+    /// the only key allowed to update the config
+    pub admin: Pubkey,
+    /// key, which has been offered the admin role and has yet to accept it
+    pub pending_admin: Option<Pubkey>,
+    /// number of slots after the last sync or heartbeat, once elapsed the record is stale
+    pub record_ttl_slots: u64,
+    /// number of slots after the record has become stale, once elapsed the record can be reaped
+    pub reap_grace_slots: u64,
+    /// share of the record's rent (in basis points) paid to whoever reaps the expired record
+    pub reap_bounty_bps: u16,
+    /// if set, only ER nodes approved by the admin can register
+    pub permissioned: bool,
+    /// minimum amount of lamports ER node operator has to bond in order to register, 0 if
+    /// bonding isn't required
+    pub min_bond_lamports: u64,
+    /// number of slots the bond stays locked after the unlock request
+    pub bond_cooldown_slots: u64,
+    /// if set, registration and sync require the feature registry and reject
+    /// the records with features, which aren't defined in it
+    pub require_defined_features: bool,
+    /// slot at which the config has been initialized, None until then. Legacy (V0) records,
+    /// which don't track liveness, are considered last seen at this slot
+    pub initialized_slot: Option<u64>,
+}
+
+/// Versioned program config layout, so that the config can be extended
+/// in the future while the existing account remains readable
+#[derive(BorshSerialize, BorshDeserialize)]
+enum VersionedConfig {
+    V0(ProgramConfig),
+}
+
+impl VersionedConfig {
+    fn into_latest(self) -> ProgramConfig {
+        match self {
+            Self::V0(v) => v,
+        }
+    }
+}
+
+/// Modifications of the program config, None fields are left intact
+#[derive(Debug, Default, BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq)]
+pub struct ConfigUpdate {
+    /// offers the admin role to the given key, which has to accept it to take over,
+    /// `Some(None)` cancels the pending admin transfer
+    pub pending_admin: Option<Option<Pubkey>>,
+    pub record_ttl_slots: Option<u64>,
+    pub reap_grace_slots: Option<u64>,
+    pub reap_bounty_bps: Option<u16>,
+    pub permissioned: Option<bool>,
+    pub min_bond_lamports: Option<u64>,
+    pub bond_cooldown_slots: Option<u64>,
+    pub require_defined_features: Option<bool>,
+}
+
+impl Default for ProgramConfig {
+    fn default() -> Self {
+        Self {
+            admin: Pubkey::default(),
+            pending_admin: None,
+            record_ttl_slots: DEFAULT_RECORD_TTL_SLOTS,
+            reap_grace_slots: DEFAULT_REAP_GRACE_SLOTS,
+            reap_bounty_bps: DEFAULT_REAP_BOUNTY_BPS,
+            permissioned: false,
+            min_bond_lamports: 0,
+            bond_cooldown_slots: DEFAULT_BOND_COOLDOWN_SLOTS,
+            require_defined_features: false,
+            initialized_slot: None,
+        }
+    }
+}
+
+impl ProgramConfig {
+    /// Creates config with the default policy, managed by the given admin
+    pub fn new(admin: Pubkey, slot: u64) -> Self {
+        Self {
+            admin,
+            initialized_slot: Some(slot),
+            ..Default::default()
+        }
+    }
+
+    /// Computes program config's PDA
+    pub fn pda() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CONFIG_SEED], &ID)
+    }
+
+    /// Computes the address of the program data account, which holds the upgrade
+    /// authority of the program, the only key allowed to initialize the config
+    pub fn program_data_address() -> Pubkey {
+        Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+    }
+
+    /// Deserializes the config from account data
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, MdpError> {
+        data.strip_prefix(CONFIG_DISCRIMINATOR.as_slice())
+            .and_then(|data| VersionedConfig::try_from_slice(data).ok())
+            .map(VersionedConfig::into_latest)
+            .ok_or(MdpError::InvalidRecordData)
+    }
+
+    /// Serializes the config into account data, prefixed with discriminator
+    pub fn to_account_data(&self) -> borsh::io::Result<Vec<u8>> {
+        let mut data = CONFIG_DISCRIMINATOR.to_vec();
+        VersionedConfig::V0(self.clone()).serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Checks that the policy values are within their valid ranges
+    pub fn validate(&self) -> Result<(), MdpError> {
+        if self.record_ttl_slots == 0 || self.reap_bounty_bps > MAX_BPS {
+            return Err(MdpError::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    /// Applies the modifications to the config, validating the result
+    pub fn apply(&mut self, update: ConfigUpdate) -> Result<(), MdpError> {
+        let mut config = self.clone();
+        if let Some(admin) = update.pending_admin {
+            config.pending_admin = admin;
+        }
+        if let Some(ttl) = update.record_ttl_slots {
+            config.record_ttl_slots = ttl;
+        }
+        if let Some(grace) = update.reap_grace_slots {
+            config.reap_grace_slots = grace;
+        }
+        if let Some(bps) = update.reap_bounty_bps {
+            config.reap_bounty_bps = bps;
+        }
+        if let Some(permissioned) = update.permissioned {
+            config.permissioned = permissioned;
+        }
+        if let Some(lamports) = update.min_bond_lamports {
+            config.min_bond_lamports = lamports;
+        }
+        if let Some(cooldown) = update.bond_cooldown_slots {
+            config.bond_cooldown_slots = cooldown;
+        }
+        if let Some(required) = update.require_defined_features {
+            config.require_defined_features = required;
+        }
+        config.validate()?;
+        *self = config;
+        Ok(())
+    }
+
+    /// Completes the admin transfer, if the given key is the pending admin
+    pub fn accept_admin(&mut self, key: &Pubkey) -> Result<(), MdpError> {
+        if self.pending_admin.as_ref() != Some(key) {
+            return Err(MdpError::AdminMismatch);
+        }
+        self.admin = *key;
+        self.pending_admin = None;
+        Ok(())
+    }
+
+    /// Computes the bounty paid from the given record rent to whoever reaps it
+    pub fn reap_bounty(&self, rent: u64) -> u64 {
+        (rent as u128 * self.reap_bounty_bps as u128 / MAX_BPS as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_update() {
+        let admin = Pubkey::new_unique();
+        let mut config = ProgramConfig::new(admin, 0);
+        let update = ConfigUpdate {
+            record_ttl_slots: Some(100),
+            reap_bounty_bps: Some(MAX_BPS + 1),
+            ..Default::default()
+        };
+        assert_eq!(config.apply(update), Err(MdpError::InvalidConfig));
+        assert_eq!(config.record_ttl_slots, DEFAULT_RECORD_TTL_SLOTS);
+
+        let new_admin = Pubkey::new_unique();
+        let update = ConfigUpdate {
+            pending_admin: Some(Some(new_admin)),
+            reap_bounty_bps: Some(MAX_BPS),
+            ..Default::default()
+        };
+        config.apply(update).unwrap();
+        assert_eq!(config.reap_bounty(1_000), 1_000);
+        assert_eq!(config.admin, admin);
+
+        assert_eq!(config.accept_admin(&admin), Err(MdpError::AdminMismatch));
+        config.accept_admin(&new_admin).unwrap();
+        assert_eq!(config.admin, new_admin);
+        assert_eq!(config.pending_admin, None);
+
+        let update = ConfigUpdate {
+            pending_admin: Some(Some(admin)),
+            ..Default::default()
+        };
+        config.apply(update).unwrap();
+        let update = ConfigUpdate {
+            pending_admin: Some(None),
+            ..Default::default()
+        };
+        config.apply(update).unwrap();
+        assert_eq!(config.accept_admin(&admin), Err(MdpError::AdminMismatch));
+
+        let data = config.to_account_data().unwrap();
+        assert_eq!(ProgramConfig::try_from_account_data(&data), Ok(config));
+    }
+}
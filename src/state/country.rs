@@ -0,0 +1,396 @@
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::error::MdpError;
+
+/// ISO 3166-1 country code, where ER node is deployed, stored either
+/// in numeric (e.g. "276") or in alpha-3 (e.g. "DEU") form
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountryCode([u8; 3]);
+
+impl CountryCode {
+    /// Creates country code from ISO 3166-1 numeric code, e.g. 276
+    pub fn from_numeric(code: u16) -> Result<Self, MdpError> {
+        let (_, _, numeric) = find(|&(_, _, n)| n == code)?;
+        let digits = |d: u16| b'0' + (numeric / d % 10) as u8;
+        Ok(Self([digits(100), digits(10), digits(1)]))
+    }
+
+    /// Creates country code from ISO 3166-1 alpha-2 code (case insensitive), e.g. "DE",
+    /// the code is stored in alpha-3 form, as there's no space for the original one
+    pub fn from_alpha2(code: &str) -> Result<Self, MdpError> {
+        let code = code.as_bytes();
+        find(|(a2, _, _)| a2.eq_ignore_ascii_case(code)).map(|&(_, a3, _)| Self(a3))
+    }
+
+    /// Creates country code from ISO 3166-1 alpha-3 code (case insensitive), e.g. "DEU"
+    pub fn from_alpha3(code: &str) -> Result<Self, MdpError> {
+        let code = code.as_bytes();
+        find(|(_, a3, _)| a3.eq_ignore_ascii_case(code)).map(|&(_, a3, _)| Self(a3))
+    }
+
+    /// Checks whether the stored code is a valid ISO 3166-1 numeric or alpha-3 code
+    pub fn validate(&self) -> Result<(), MdpError> {
+        self.entry().map(|_| ())
+    }
+
+    /// Returns ISO 3166-1 numeric code of the country, None if the stored code is invalid
+    pub fn numeric(&self) -> Option<u16> {
+        self.entry().ok().map(|&(_, _, n)| n)
+    }
+
+    /// Returns ISO 3166-1 alpha-2 code of the country, None if the stored code is invalid
+    pub fn alpha2(&self) -> Option<&'static str> {
+        let (a2, _, _) = self.entry().ok()?;
+        std::str::from_utf8(a2).ok()
+    }
+
+    /// Returns ISO 3166-1 alpha-3 code of the country, None if the stored code is invalid
+    pub fn alpha3(&self) -> Option<&'static str> {
+        let (_, a3, _) = self.entry().ok()?;
+        std::str::from_utf8(a3).ok()
+    }
+
+    /// Returns the code in the form it's stored, None if it's not a valid UTF-8 string
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.0).ok()
+    }
+
+    /// Looks up the country in ISO 3166-1 table by the stored code
+    fn entry(&self) -> Result<&'static Country, MdpError> {
+        if self.0.iter().all(u8::is_ascii_digit) {
+            let numeric = self.0.iter().fold(0, |n, d| n * 10 + (d - b'0') as u16);
+            find(|&(_, _, n)| n == numeric)
+        } else {
+            find(|(_, a3, _)| *a3 == self.0)
+        }
+    }
+}
+
+impl TryFrom<&str> for CountryCode {
+    type Error = MdpError;
+
+    /// Parses any of ISO 3166-1 alpha-2, alpha-3 or numeric codes
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        match code.len() {
+            2 => Self::from_alpha2(code),
+            3 if code.bytes().all(|b| b.is_ascii_digit()) => {
+                let numeric = code.parse().map_err(|_| MdpError::InvalidCountryCode)?;
+                Self::from_numeric(numeric)
+            }
+            3 => Self::from_alpha3(code),
+            _ => Err(MdpError::InvalidCountryCode),
+        }
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = MdpError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::try_from(code)
+    }
+}
+
+/// ISO 3166-1 entry: alpha-2, alpha-3 and numeric codes of the country
+type Country = ([u8; 2], [u8; 3], u16);
+
+fn find(predicate: impl Fn(&Country) -> bool) -> Result<&'static Country, MdpError> {
+    COUNTRIES
+        .iter()
+        .find(|c| predicate(c))
+        .ok_or(MdpError::InvalidCountryCode)
+}
+
+#[rustfmt::skip]
+const COUNTRIES: &[Country] = &[
+    (*b"AF", *b"AFG", 4), // Afghanistan
+    (*b"AL", *b"ALB", 8), // Albania
+    (*b"AQ", *b"ATA", 10), // Antarctica
+    (*b"DZ", *b"DZA", 12), // Algeria
+    (*b"AS", *b"ASM", 16), // American Samoa
+    (*b"AD", *b"AND", 20), // Andorra
+    (*b"AO", *b"AGO", 24), // Angola
+    (*b"AG", *b"ATG", 28), // Antigua and Barbuda
+    (*b"AZ", *b"AZE", 31), // Azerbaijan
+    (*b"AR", *b"ARG", 32), // Argentina
+    (*b"AU", *b"AUS", 36), // Australia
+    (*b"AT", *b"AUT", 40), // Austria
+    (*b"BS", *b"BHS", 44), // Bahamas
+    (*b"BH", *b"BHR", 48), // Bahrain
+    (*b"BD", *b"BGD", 50), // Bangladesh
+    (*b"AM", *b"ARM", 51), // Armenia
+    (*b"BB", *b"BRB", 52), // Barbados
+    (*b"BE", *b"BEL", 56), // Belgium
+    (*b"BM", *b"BMU", 60), // Bermuda
+    (*b"BT", *b"BTN", 64), // Bhutan
+    (*b"BO", *b"BOL", 68), // Bolivia, Plurinational State of
+    (*b"BA", *b"BIH", 70), // Bosnia and Herzegovina
+    (*b"BW", *b"BWA", 72), // Botswana
+    (*b"BV", *b"BVT", 74), // Bouvet Island
+    (*b"BR", *b"BRA", 76), // Brazil
+    (*b"BZ", *b"BLZ", 84), // Belize
+    (*b"IO", *b"IOT", 86), // British Indian Ocean Territory
+    (*b"SB", *b"SLB", 90), // Solomon Islands
+    (*b"VG", *b"VGB", 92), // Virgin Islands, British
+    (*b"BN", *b"BRN", 96), // Brunei Darussalam
+    (*b"BG", *b"BGR", 100), // Bulgaria
+    (*b"MM", *b"MMR", 104), // Myanmar
+    (*b"BI", *b"BDI", 108), // Burundi
+    (*b"BY", *b"BLR", 112), // Belarus
+    (*b"KH", *b"KHM", 116), // Cambodia
+    (*b"CM", *b"CMR", 120), // Cameroon
+    (*b"CA", *b"CAN", 124), // Canada
+    (*b"CV", *b"CPV", 132), // Cabo Verde
+    (*b"KY", *b"CYM", 136), // Cayman Islands
+    (*b"CF", *b"CAF", 140), // Central African Republic
+    (*b"LK", *b"LKA", 144), // Sri Lanka
+    (*b"TD", *b"TCD", 148), // Chad
+    (*b"CL", *b"CHL", 152), // Chile
+    (*b"CN", *b"CHN", 156), // China
+    (*b"TW", *b"TWN", 158), // Taiwan, Province of China
+    (*b"CX", *b"CXR", 162), // Christmas Island
+    (*b"CC", *b"CCK", 166), // Cocos (Keeling) Islands
+    (*b"CO", *b"COL", 170), // Colombia
+    (*b"KM", *b"COM", 174), // Comoros
+    (*b"YT", *b"MYT", 175), // Mayotte
+    (*b"CG", *b"COG", 178), // Congo
+    (*b"CD", *b"COD", 180), // Congo, The Democratic Republic of the
+    (*b"CK", *b"COK", 184), // Cook Islands
+    (*b"CR", *b"CRI", 188), // Costa Rica
+    (*b"HR", *b"HRV", 191), // Croatia
+    (*b"CU", *b"CUB", 192), // Cuba
+    (*b"CY", *b"CYP", 196), // Cyprus
+    (*b"CZ", *b"CZE", 203), // Czechia
+    (*b"BJ", *b"BEN", 204), // Benin
+    (*b"DK", *b"DNK", 208), // Denmark
+    (*b"DM", *b"DMA", 212), // Dominica
+    (*b"DO", *b"DOM", 214), // Dominican Republic
+    (*b"EC", *b"ECU", 218), // Ecuador
+    (*b"SV", *b"SLV", 222), // El Salvador
+    (*b"GQ", *b"GNQ", 226), // Equatorial Guinea
+    (*b"ET", *b"ETH", 231), // Ethiopia
+    (*b"ER", *b"ERI", 232), // Eritrea
+    (*b"EE", *b"EST", 233), // Estonia
+    (*b"FO", *b"FRO", 234), // Faroe Islands
+    (*b"FK", *b"FLK", 238), // Falkland Islands (Malvinas)
+    (*b"GS", *b"SGS", 239), // South Georgia and the South Sandwich Islands
+    (*b"FJ", *b"FJI", 242), // Fiji
+    (*b"FI", *b"FIN", 246), // Finland
+    (*b"AX", *b"ALA", 248), // Åland Islands
+    (*b"FR", *b"FRA", 250), // France
+    (*b"GF", *b"GUF", 254), // French Guiana
+    (*b"PF", *b"PYF", 258), // French Polynesia
+    (*b"TF", *b"ATF", 260), // French Southern Territories
+    (*b"DJ", *b"DJI", 262), // Djibouti
+    (*b"GA", *b"GAB", 266), // Gabon
+    (*b"GE", *b"GEO", 268), // Georgia
+    (*b"GM", *b"GMB", 270), // Gambia
+    (*b"PS", *b"PSE", 275), // Palestine, State of
+    (*b"DE", *b"DEU", 276), // Germany
+    (*b"GH", *b"GHA", 288), // Ghana
+    (*b"GI", *b"GIB", 292), // Gibraltar
+    (*b"KI", *b"KIR", 296), // Kiribati
+    (*b"GR", *b"GRC", 300), // Greece
+    (*b"GL", *b"GRL", 304), // Greenland
+    (*b"GD", *b"GRD", 308), // Grenada
+    (*b"GP", *b"GLP", 312), // Guadeloupe
+    (*b"GU", *b"GUM", 316), // Guam
+    (*b"GT", *b"GTM", 320), // Guatemala
+    (*b"GN", *b"GIN", 324), // Guinea
+    (*b"GY", *b"GUY", 328), // Guyana
+    (*b"HT", *b"HTI", 332), // Haiti
+    (*b"HM", *b"HMD", 334), // Heard Island and McDonald Islands
+    (*b"VA", *b"VAT", 336), // Holy See (Vatican City State)
+    (*b"HN", *b"HND", 340), // Honduras
+    (*b"HK", *b"HKG", 344), // Hong Kong
+    (*b"HU", *b"HUN", 348), // Hungary
+    (*b"IS", *b"ISL", 352), // Iceland
+    (*b"IN", *b"IND", 356), // India
+    (*b"ID", *b"IDN", 360), // Indonesia
+    (*b"IR", *b"IRN", 364), // Iran, Islamic Republic of
+    (*b"IQ", *b"IRQ", 368), // Iraq
+    (*b"IE", *b"IRL", 372), // Ireland
+    (*b"IL", *b"ISR", 376), // Israel
+    (*b"IT", *b"ITA", 380), // Italy
+    (*b"CI", *b"CIV", 384), // Côte d'Ivoire
+    (*b"JM", *b"JAM", 388), // Jamaica
+    (*b"JP", *b"JPN", 392), // Japan
+    (*b"KZ", *b"KAZ", 398), // Kazakhstan
+    (*b"JO", *b"JOR", 400), // Jordan
+    (*b"KE", *b"KEN", 404), // Kenya
+    (*b"KP", *b"PRK", 408), // Korea, Democratic People's Republic of
+    (*b"KR", *b"KOR", 410), // Korea, Republic of
+    (*b"KW", *b"KWT", 414), // Kuwait
+    (*b"KG", *b"KGZ", 417), // Kyrgyzstan
+    (*b"LA", *b"LAO", 418), // Lao People's Democratic Republic
+    (*b"LB", *b"LBN", 422), // Lebanon
+    (*b"LS", *b"LSO", 426), // Lesotho
+    (*b"LV", *b"LVA", 428), // Latvia
+    (*b"LR", *b"LBR", 430), // Liberia
+    (*b"LY", *b"LBY", 434), // Libya
+    (*b"LI", *b"LIE", 438), // Liechtenstein
+    (*b"LT", *b"LTU", 440), // Lithuania
+    (*b"LU", *b"LUX", 442), // Luxembourg
+    (*b"MO", *b"MAC", 446), // Macao
+    (*b"MG", *b"MDG", 450), // Madagascar
+    (*b"MW", *b"MWI", 454), // Malawi
+    (*b"MY", *b"MYS", 458), // Malaysia
+    (*b"MV", *b"MDV", 462), // Maldives
+    (*b"ML", *b"MLI", 466), // Mali
+    (*b"MT", *b"MLT", 470), // Malta
+    (*b"MQ", *b"MTQ", 474), // Martinique
+    (*b"MR", *b"MRT", 478), // Mauritania
+    (*b"MU", *b"MUS", 480), // Mauritius
+    (*b"MX", *b"MEX", 484), // Mexico
+    (*b"MC", *b"MCO", 492), // Monaco
+    (*b"MN", *b"MNG", 496), // Mongolia
+    (*b"MD", *b"MDA", 498), // Moldova, Republic of
+    (*b"ME", *b"MNE", 499), // Montenegro
+    (*b"MS", *b"MSR", 500), // Montserrat
+    (*b"MA", *b"MAR", 504), // Morocco
+    (*b"MZ", *b"MOZ", 508), // Mozambique
+    (*b"OM", *b"OMN", 512), // Oman
+    (*b"NA", *b"NAM", 516), // Namibia
+    (*b"NR", *b"NRU", 520), // Nauru
+    (*b"NP", *b"NPL", 524), // Nepal
+    (*b"NL", *b"NLD", 528), // Netherlands
+    (*b"CW", *b"CUW", 531), // Curaçao
+    (*b"AW", *b"ABW", 533), // Aruba
+    (*b"SX", *b"SXM", 534), // Sint Maarten (Dutch part)
+    (*b"BQ", *b"BES", 535), // Bonaire, Sint Eustatius and Saba
+    (*b"NC", *b"NCL", 540), // New Caledonia
+    (*b"VU", *b"VUT", 548), // Vanuatu
+    (*b"NZ", *b"NZL", 554), // New Zealand
+    (*b"NI", *b"NIC", 558), // Nicaragua
+    (*b"NE", *b"NER", 562), // Niger
+    (*b"NG", *b"NGA", 566), // Nigeria
+    (*b"NU", *b"NIU", 570), // Niue
+    (*b"NF", *b"NFK", 574), // Norfolk Island
+    (*b"NO", *b"NOR", 578), // Norway
+    (*b"MP", *b"MNP", 580), // Northern Mariana Islands
+    (*b"UM", *b"UMI", 581), // United States Minor Outlying Islands
+    (*b"FM", *b"FSM", 583), // Micronesia, Federated States of
+    (*b"MH", *b"MHL", 584), // Marshall Islands
+    (*b"PW", *b"PLW", 585), // Palau
+    (*b"PK", *b"PAK", 586), // Pakistan
+    (*b"PA", *b"PAN", 591), // Panama
+    (*b"PG", *b"PNG", 598), // Papua New Guinea
+    (*b"PY", *b"PRY", 600), // Paraguay
+    (*b"PE", *b"PER", 604), // Peru
+    (*b"PH", *b"PHL", 608), // Philippines
+    (*b"PN", *b"PCN", 612), // Pitcairn
+    (*b"PL", *b"POL", 616), // Poland
+    (*b"PT", *b"PRT", 620), // Portugal
+    (*b"GW", *b"GNB", 624), // Guinea-Bissau
+    (*b"TL", *b"TLS", 626), // Timor-Leste
+    (*b"PR", *b"PRI", 630), // Puerto Rico
+    (*b"QA", *b"QAT", 634), // Qatar
+    (*b"RE", *b"REU", 638), // Réunion
+    (*b"RO", *b"ROU", 642), // Romania
+    (*b"RU", *b"RUS", 643), // Russian Federation
+    (*b"RW", *b"RWA", 646), // Rwanda
+    (*b"BL", *b"BLM", 652), // Saint Barthélemy
+    (*b"SH", *b"SHN", 654), // Saint Helena, Ascension and Tristan da Cunha
+    (*b"KN", *b"KNA", 659), // Saint Kitts and Nevis
+    (*b"AI", *b"AIA", 660), // Anguilla
+    (*b"LC", *b"LCA", 662), // Saint Lucia
+    (*b"MF", *b"MAF", 663), // Saint Martin (French part)
+    (*b"PM", *b"SPM", 666), // Saint Pierre and Miquelon
+    (*b"VC", *b"VCT", 670), // Saint Vincent and the Grenadines
+    (*b"SM", *b"SMR", 674), // San Marino
+    (*b"ST", *b"STP", 678), // Sao Tome and Principe
+    (*b"SA", *b"SAU", 682), // Saudi Arabia
+    (*b"SN", *b"SEN", 686), // Senegal
+    (*b"RS", *b"SRB", 688), // Serbia
+    (*b"SC", *b"SYC", 690), // Seychelles
+    (*b"SL", *b"SLE", 694), // Sierra Leone
+    (*b"SG", *b"SGP", 702), // Singapore
+    (*b"SK", *b"SVK", 703), // Slovakia
+    (*b"VN", *b"VNM", 704), // Viet Nam
+    (*b"SI", *b"SVN", 705), // Slovenia
+    (*b"SO", *b"SOM", 706), // Somalia
+    (*b"ZA", *b"ZAF", 710), // South Africa
+    (*b"ZW", *b"ZWE", 716), // Zimbabwe
+    (*b"ES", *b"ESP", 724), // Spain
+    (*b"SS", *b"SSD", 728), // South Sudan
+    (*b"SD", *b"SDN", 729), // Sudan
+    (*b"EH", *b"ESH", 732), // Western Sahara
+    (*b"SR", *b"SUR", 740), // Suriname
+    (*b"SJ", *b"SJM", 744), // Svalbard and Jan Mayen
+    (*b"SZ", *b"SWZ", 748), // Eswatini
+    (*b"SE", *b"SWE", 752), // Sweden
+    (*b"CH", *b"CHE", 756), // Switzerland
+    (*b"SY", *b"SYR", 760), // Syrian Arab Republic
+    (*b"TJ", *b"TJK", 762), // Tajikistan
+    (*b"TH", *b"THA", 764), // Thailand
+    (*b"TG", *b"TGO", 768), // Togo
+    (*b"TK", *b"TKL", 772), // Tokelau
+    (*b"TO", *b"TON", 776), // Tonga
+    (*b"TT", *b"TTO", 780), // Trinidad and Tobago
+    (*b"AE", *b"ARE", 784), // United Arab Emirates
+    (*b"TN", *b"TUN", 788), // Tunisia
+    (*b"TR", *b"TUR", 792), // Türkiye
+    (*b"TM", *b"TKM", 795), // Turkmenistan
+    (*b"TC", *b"TCA", 796), // Turks and Caicos Islands
+    (*b"TV", *b"TUV", 798), // Tuvalu
+    (*b"UG", *b"UGA", 800), // Uganda
+    (*b"UA", *b"UKR", 804), // Ukraine
+    (*b"MK", *b"MKD", 807), // North Macedonia
+    (*b"EG", *b"EGY", 818), // Egypt
+    (*b"GB", *b"GBR", 826), // United Kingdom
+    (*b"GG", *b"GGY", 831), // Guernsey
+    (*b"JE", *b"JEY", 832), // Jersey
+    (*b"IM", *b"IMN", 833), // Isle of Man
+    (*b"TZ", *b"TZA", 834), // Tanzania, United Republic of
+    (*b"US", *b"USA", 840), // United States
+    (*b"VI", *b"VIR", 850), // Virgin Islands, U.S.
+    (*b"BF", *b"BFA", 854), // Burkina Faso
+    (*b"UY", *b"URY", 858), // Uruguay
+    (*b"UZ", *b"UZB", 860), // Uzbekistan
+    (*b"VE", *b"VEN", 862), // Venezuela, Bolivarian Republic of
+    (*b"WF", *b"WLF", 876), // Wallis and Futuna
+    (*b"WS", *b"WSM", 882), // Samoa
+    (*b"YE", *b"YEM", 887), // Yemen
+    (*b"ZM", *b"ZMB", 894), // Zambia
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_code_conversions() {
+        let numeric = CountryCode::try_from("276").unwrap();
+        let alpha2 = CountryCode::try_from("de").unwrap();
+        let alpha3 = CountryCode::try_from("DEU").unwrap();
+        for code in [numeric, alpha2, alpha3] {
+            assert_eq!(code.validate(), Ok(()));
+            assert_eq!(code.numeric(), Some(276));
+            assert_eq!(code.alpha2(), Some("DE"));
+            assert_eq!(code.alpha3(), Some("DEU"));
+        }
+        assert_eq!(numeric.as_str(), Some("276"));
+        assert_eq!(alpha2.as_str(), Some("DEU"));
+        assert_eq!(CountryCode::from_numeric(4).unwrap().as_str(), Some("004"));
+        assert_eq!("USA".parse::<CountryCode>().unwrap().numeric(), Some(840));
+    }
+
+    #[test]
+    fn test_invalid_country_codes() {
+        for code in [
+            "", "D", "083", "XXX", "XX", "DEUT", "27", "2760", "de ", "１２",
+        ] {
+            let result = CountryCode::try_from(code);
+            assert_eq!(result, Err(MdpError::InvalidCountryCode), "{code}");
+        }
+        assert_eq!(
+            CountryCode::from_numeric(999),
+            Err(MdpError::InvalidCountryCode)
+        );
+        let garbage = CountryCode([0xff, b'E', b'U']);
+        assert_eq!(garbage.validate(), Err(MdpError::InvalidCountryCode));
+        assert_eq!(garbage.as_str(), None);
+    }
+}
@@ -0,0 +1,240 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    consts::{
+        ALLOWED_ADDR_SCHEMES, ALLOWED_CUSTOM_SCHEMES, ALLOWED_GRPC_SCHEMES,
+        ALLOWED_METRICS_SCHEMES, ALLOWED_WS_SCHEMES, MAX_ENDPOINTS,
+    },
+    error::MdpError,
+};
+
+use super::addr::validate_url;
+
+/// Kind of the service exposed by ER node over the endpoint
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceKind {
+    /// JSON-RPC API
+    Rpc,
+    /// websocket subscriptions
+    Ws,
+    /// gRPC API
+    Grpc,
+    /// monitoring metrics
+    Metrics,
+    /// application specific service, identified by the given number
+    Custom(u16),
+}
+
+impl ServiceKind {
+    /// URL schemes which are allowed in the address of the endpoint of this kind
+    pub fn allowed_schemes(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rpc => ALLOWED_ADDR_SCHEMES,
+            Self::Ws => ALLOWED_WS_SCHEMES,
+            Self::Grpc => ALLOWED_GRPC_SCHEMES,
+            Self::Metrics => ALLOWED_METRICS_SCHEMES,
+            Self::Custom(_) => ALLOWED_CUSTOM_SCHEMES,
+        }
+    }
+}
+
+/// Network endpoint of a service exposed by ER node, similar to DNS SRV record
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Kind of the service available at the endpoint
+    pub kind: ServiceKind,
+    /// Endpoints with lower priority value should be preferred by the clients
+    pub priority: u16,
+    /// Relative weight for the load balancing among endpoints with the same priority
+    pub weight: u16,
+    /// URL of the endpoint
+    pub addr: String,
+}
+
+impl Endpoint {
+    /// Creates JSON-RPC endpoint with the highest priority
+    pub fn rpc(addr: String) -> Self {
+        Self {
+            kind: ServiceKind::Rpc,
+            priority: 0,
+            weight: 0,
+            addr,
+        }
+    }
+
+    /// Returns true if the endpoint is identified by the given kind and address
+    fn is(&self, kind: ServiceKind, addr: &str) -> bool {
+        self.kind == kind && self.addr == addr
+    }
+}
+
+/// Modification of a single endpoint in the record, endpoints are identified by kind and address
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, PartialEq, Eq)]
+pub enum EndpointUpdate {
+    /// Adds the new endpoint to the record
+    Add(Endpoint),
+    /// Removes the endpoint with the given kind and address
+    Remove { kind: ServiceKind, addr: String },
+    /// Replaces the endpoint with the given kind and address with the new one
+    Replace {
+        kind: ServiceKind,
+        addr: String,
+        endpoint: Endpoint,
+    },
+}
+
+impl EndpointUpdate {
+    /// Applies the modification to the list of endpoints, the
+    /// resulting list should be validated with [`validate_endpoints`]
+    pub fn apply(self, endpoints: &mut Vec<Endpoint>) -> Result<(), MdpError> {
+        let find = |endpoints: &[Endpoint], kind, addr: &str| {
+            endpoints
+                .iter()
+                .position(|e| e.is(kind, addr))
+                .ok_or(MdpError::EndpointNotFound)
+        };
+        match self {
+            Self::Add(endpoint) => endpoints.push(endpoint),
+            Self::Remove { kind, addr } => {
+                endpoints.remove(find(endpoints, kind, &addr)?);
+            }
+            Self::Replace {
+                kind,
+                addr,
+                endpoint,
+            } => {
+                let i = find(endpoints, kind, &addr)?;
+                endpoints[i] = endpoint;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the primary JSON-RPC endpoint, i.e. the first one with the lowest priority value
+pub fn primary_rpc(endpoints: &[Endpoint]) -> Option<&Endpoint> {
+    endpoints
+        .iter()
+        .filter(|e| e.kind == ServiceKind::Rpc)
+        .min_by_key(|e| e.priority)
+}
+
+/// Replaces the address of the primary JSON-RPC endpoint, adding one if there's none
+pub fn set_primary_rpc(endpoints: &mut Vec<Endpoint>, addr: String) {
+    let primary = endpoints
+        .iter_mut()
+        .filter(|e| e.kind == ServiceKind::Rpc)
+        .min_by_key(|e| e.priority);
+    match primary {
+        Some(primary) => primary.addr = addr,
+        None => endpoints.push(Endpoint::rpc(addr)),
+    }
+}
+
+/// Validates the list of endpoints: it should contain at least one JSON-RPC endpoint,
+/// at most [`MAX_ENDPOINTS`] entries, with no duplicates and only valid addresses, whose
+/// schemes are allowed for the kind of the endpoint (see [`ServiceKind::allowed_schemes`])
+pub fn validate_endpoints(endpoints: &[Endpoint]) -> Result<(), MdpError> {
+    if endpoints.len() > MAX_ENDPOINTS || primary_rpc(endpoints).is_none() {
+        return Err(MdpError::InvalidEndpoint);
+    }
+    for (i, endpoint) in endpoints.iter().enumerate() {
+        validate_url(&endpoint.addr, endpoint.kind.allowed_schemes())?;
+        if endpoints[..i]
+            .iter()
+            .any(|e| e.is(endpoint.kind, &endpoint.addr))
+        {
+            return Err(MdpError::InvalidEndpoint);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(kind: ServiceKind, priority: u16, addr: &str) -> Endpoint {
+        Endpoint {
+            kind,
+            priority,
+            weight: 0,
+            addr: addr.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_updates() {
+        let mut endpoints = vec![Endpoint::rpc("https://rpc.er.app".to_string())];
+        let ws = endpoint(ServiceKind::Ws, 0, "wss://ws.er.app");
+        let grpc = endpoint(ServiceKind::Grpc, 0, "http://grpc.er.app:9000");
+        let rpc = endpoint(ServiceKind::Rpc, 1, "https://rpc2.er.app");
+
+        EndpointUpdate::Add(ws.clone())
+            .apply(&mut endpoints)
+            .unwrap();
+        EndpointUpdate::Add(rpc.clone())
+            .apply(&mut endpoints)
+            .unwrap();
+        let replace = EndpointUpdate::Replace {
+            kind: ServiceKind::Ws,
+            addr: ws.addr.clone(),
+            endpoint: grpc.clone(),
+        };
+        replace.apply(&mut endpoints).unwrap();
+        let remove = EndpointUpdate::Remove {
+            kind: ServiceKind::Rpc,
+            addr: "https://rpc.er.app".to_string(),
+        };
+        remove.clone().apply(&mut endpoints).unwrap();
+
+        assert_eq!(endpoints, [grpc, rpc.clone()]);
+        assert_eq!(primary_rpc(&endpoints), Some(&rpc));
+        assert_eq!(validate_endpoints(&endpoints), Ok(()));
+        assert_eq!(
+            remove.apply(&mut endpoints),
+            Err(MdpError::EndpointNotFound)
+        );
+    }
+
+    #[test]
+    fn test_invalid_endpoints() {
+        let rpc = endpoint(ServiceKind::Rpc, 0, "https://rpc.er.app");
+        let ws = endpoint(ServiceKind::Ws, 0, "wss://ws.er.app");
+        let invalid = endpoint(ServiceKind::Ws, 0, "wss://ws er.app");
+        let metrics = endpoint(ServiceKind::Metrics, 0, "http://er.app:9090/metrics");
+
+        assert_eq!(validate_endpoints(&[]), Err(MdpError::InvalidEndpoint));
+        assert_eq!(
+            validate_endpoints(std::slice::from_ref(&ws)),
+            Err(MdpError::InvalidEndpoint)
+        );
+        assert_eq!(
+            validate_endpoints(&[rpc.clone(), ws.clone(), ws]),
+            Err(MdpError::InvalidEndpoint)
+        );
+        assert_eq!(
+            validate_endpoints(&[rpc.clone(), invalid]),
+            Err(MdpError::InvalidAddr)
+        );
+        assert_eq!(validate_endpoints(&[rpc.clone(), metrics.clone()]), Ok(()));
+        // plaintext is only allowed for the kinds which are commonly served without TLS
+        let plain_ws = Endpoint {
+            kind: ServiceKind::Ws,
+            ..metrics.clone()
+        };
+        let plain_rpc = Endpoint {
+            kind: ServiceKind::Rpc,
+            ..metrics
+        };
+        assert_eq!(
+            validate_endpoints(&[rpc.clone(), plain_ws]),
+            Err(MdpError::InvalidAddr)
+        );
+        assert_eq!(validate_endpoints(&[plain_rpc]), Err(MdpError::InvalidAddr));
+        assert_eq!(
+            validate_endpoints(&vec![rpc; MAX_ENDPOINTS + 1]),
+            Err(MdpError::InvalidEndpoint)
+        );
+    }
+}
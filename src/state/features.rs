@@ -1,7 +1,9 @@
-use std::ops::BitOrAssign;
+use std::{fmt, str::FromStr};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
+use crate::error::MdpError;
+
 /// Number of bytes used for feature flags, we set this value
 /// to 32, this gives 256 bits, i.e. 256 different features
 const FEATURESET_BYTES: usize = 32;
@@ -11,11 +13,80 @@ const FEATURESET_BYTES: usize = 32;
 pub struct FeaturesSet([u8; FEATURESET_BYTES]);
 
 /// Individual custom extra feature supported by validator
-#[derive(Clone, Copy)]
-#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Feature {
-    Randomness = 0,
-    HighResClock = 1,
+    Randomness,
+    HighResClock,
+    /// Feature, which is not known to this version of the crate, identified by its bit index
+    Unknown(u8),
+}
+
+impl Feature {
+    /// Known features along with their canonical names
+    const NAMED: [(Self, &'static str); 2] = [
+        (Self::Randomness, "randomness"),
+        (Self::HighResClock, "high-res-clock"),
+    ];
+
+    /// Returns the index of the bit, which represents the feature in [`FeaturesSet`]
+    pub fn bit(self) -> u8 {
+        match self {
+            Self::Randomness => 0,
+            Self::HighResClock => 1,
+            Self::Unknown(bit) => bit,
+        }
+    }
+
+    /// Returns canonical name of the feature, None if the feature is unknown
+    pub fn name(self) -> Option<&'static str> {
+        Self::NAMED
+            .iter()
+            .find(|&&(f, _)| f.bit() == self.bit())
+            .map(|&(_, name)| name)
+    }
+
+    /// Returns true if the feature is known to this version of the crate
+    pub fn is_known(self) -> bool {
+        !matches!(Self::from(self.bit()), Self::Unknown(_))
+    }
+}
+
+/// Every bit index maps to a feature, the ones which aren't known become [`Feature::Unknown`]
+impl From<u8> for Feature {
+    fn from(bit: u8) -> Self {
+        match bit {
+            0 => Self::Randomness,
+            1 => Self::HighResClock,
+            _ => Self::Unknown(bit),
+        }
+    }
+}
+
+/// Displays canonical name of the known feature or the bit index of the unknown one
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.bit()),
+        }
+    }
+}
+
+/// Parses either canonical name of the feature or its bit index
+impl FromStr for Feature {
+    type Err = MdpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(&(feature, _)) = Self::NAMED.iter().find(|(_, name)| *name == s) {
+            return Ok(feature);
+        }
+        if !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(MdpError::InvalidFeature);
+        }
+        s.parse::<u8>()
+            .map(Self::from)
+            .map_err(|_| MdpError::InvalidFeature)
+    }
 }
 
 impl FeaturesSet {
@@ -23,44 +94,193 @@ impl FeaturesSet {
 
     /// Enables given feature in featureset
     pub fn activate(mut self, feature: Feature) -> Self {
-        let (segment, offset) = self.locate(feature);
-        segment.bitor_assign(1 << offset);
+        let (index, mask) = Self::locate(feature.bit());
+        self.0[index] |= mask;
         self
     }
 
-    #[cfg(test)]
-    fn deactivate(&mut self, feature: Feature) {
-        use std::ops::{BitAndAssign, Not};
-        let (segment, offset) = self.locate(feature);
-        segment.bitand_assign((1u8 << offset).not());
+    /// Disables given feature in featureset
+    pub fn deactivate(mut self, feature: Feature) -> Self {
+        let (index, mask) = Self::locate(feature.bit());
+        self.0[index] &= !mask;
+        self
     }
 
-    fn locate(&mut self, feature: Feature) -> (&mut u8, u8) {
-        let index = feature as usize / Self::SEGMENT;
-        let offset = feature as usize % Self::SEGMENT;
-        // SAFETY: feature cannot exceed 255 (repr(u8)), 0..255 / 64 <= 3
-        let segment = unsafe { self.0.get_unchecked_mut(index) };
-        (segment, offset as u8)
+    /// Returns index of the byte and the mask of the bit within it
+    fn locate(bit: u8) -> (usize, u8) {
+        let bit = bit as usize;
+        (bit / Self::SEGMENT, 1 << (bit % Self::SEGMENT))
     }
 
     /// Returns true if given featureset has requested feature enabled
-    pub fn contains(&mut self, feature: Feature) -> bool {
-        let (segment, offset) = self.locate(feature);
-        (*segment & (1 << offset)) >> offset == 1
+    pub fn contains(&self, feature: Feature) -> bool {
+        self.contains_bit(feature.bit())
+    }
+
+    fn contains_bit(&self, bit: u8) -> bool {
+        let (index, mask) = Self::locate(bit);
+        self.0[index] & mask != 0
+    }
+
+    /// Returns the set of features enabled in either of the sets
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Returns the set of features enabled in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Returns the set of features enabled in this set, but not in the other one
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    fn combine(&self, other: &Self, op: impl Fn(u8, u8) -> u8) -> Self {
+        Self(std::array::from_fn(|i| op(self.0[i], other.0[i])))
+    }
+
+    /// Returns true if every feature enabled in the other set is enabled in this one,
+    /// e.g. whether ER node supports all the features required by the client
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.0.iter().zip(other.0).all(|(a, b)| a & b == b)
+    }
+
+    /// Returns true if no features are enabled
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&b| b == 0)
+    }
+
+    /// Returns the number of enabled bits, including the ones, which
+    /// are not known to this version of the crate, and hence skipped by [`Self::iter`]
+    pub fn count(&self) -> usize {
+        self.0.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Iterates over the enabled known features in the order of their bit indices
+    pub fn iter(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.iter_bits().map(Feature::from).filter(|f| f.is_known())
+    }
+
+    /// Iterates over the indices of all the enabled bits, including the ones which are
+    /// not known to this version of the crate, use [`Feature::from`] to name them
+    pub fn iter_bits(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|&bit| self.contains_bit(bit))
+    }
+}
+
+impl From<&[Feature]> for FeaturesSet {
+    fn from(features: &[Feature]) -> Self {
+        features
+            .iter()
+            .fold(Self::default(), |set, &feature| set.activate(feature))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+
     #[test]
     fn test_features_op() {
-        let mut features = FeaturesSet::default()
+        let features = FeaturesSet::default()
             .activate(Feature::Randomness)
             .activate(Feature::HighResClock);
         assert!(features.contains(Feature::Randomness));
-        features.deactivate(Feature::Randomness);
+        let features = features.deactivate(Feature::Randomness);
         assert!(!features.contains(Feature::Randomness));
         assert!(features.contains(Feature::HighResClock));
     }
+
+    #[test]
+    fn test_features_iter() {
+        let features = FeaturesSet::from([Feature::HighResClock, Feature::Randomness].as_slice());
+        let collected: Vec<_> = features.iter().collect();
+        assert_eq!(collected, [Feature::Randomness, Feature::HighResClock]);
+        assert_eq!(features.count(), 2);
+        assert!(FeaturesSet::default().is_empty());
+
+        let features = features.activate(Feature::Unknown(42));
+        assert_eq!(features.iter().count(), 2);
+        let bits: Vec<_> = features.iter_bits().collect();
+        assert_eq!(bits, [0, 1, 42]);
+    }
+
+    #[test]
+    fn test_feature_names() {
+        assert_eq!(Feature::HighResClock.to_string(), "high-res-clock");
+        assert_eq!(Feature::Unknown(42).to_string(), "42");
+        assert_eq!("randomness".parse(), Ok(Feature::Randomness));
+        assert_eq!("1".parse(), Ok(Feature::HighResClock));
+        assert_eq!("255".parse(), Ok(Feature::Unknown(255)));
+        for invalid in ["", "256", "+1", "Randomness", "unknown"] {
+            assert_eq!(invalid.parse::<Feature>(), Err(MdpError::InvalidFeature));
+        }
+    }
+
+    fn features_set() -> impl Strategy<Value = FeaturesSet> {
+        any::<[u8; FEATURESET_BYTES]>().prop_map(FeaturesSet)
+    }
+
+    fn feature() -> impl Strategy<Value = Feature> {
+        any::<u8>().prop_map(Feature::from)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_activate_deactivate(set in features_set(), feature in feature()) {
+            let activated = set.clone().activate(feature);
+            prop_assert!(activated.contains(feature));
+            prop_assert!(activated.is_superset(&set));
+            let deactivated = activated.deactivate(feature);
+            prop_assert!(!deactivated.contains(feature));
+            prop_assert!(set.is_superset(&deactivated));
+        }
+
+        #[test]
+        fn prop_set_operations(a in features_set(), b in features_set()) {
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+
+            prop_assert!(union.is_superset(&a) && union.is_superset(&b));
+            prop_assert!(a.is_superset(&intersection) && b.is_superset(&intersection));
+            prop_assert!(a.is_superset(&difference));
+            prop_assert!(difference.intersection(&b).is_empty());
+            prop_assert_eq!(difference.union(&intersection), a.clone());
+            prop_assert_eq!(union.count() + intersection.count(), a.count() + b.count());
+            prop_assert_eq!(union, b.union(&a));
+            prop_assert_eq!(intersection, b.intersection(&a));
+        }
+
+        #[test]
+        fn prop_superset(a in features_set(), b in features_set()) {
+            prop_assert_eq!(a.is_superset(&b), a.union(&b) == a);
+            prop_assert_eq!(a.is_superset(&b), b.difference(&a).is_empty());
+            prop_assert!(a.is_superset(&FeaturesSet::default()));
+        }
+
+        #[test]
+        fn prop_iter(set in features_set()) {
+            let features: Vec<_> = set.iter().collect();
+            let collected = FeaturesSet::from(features.as_slice());
+            prop_assert!(set.is_superset(&collected));
+            for feature in [Feature::Randomness, Feature::HighResClock] {
+                prop_assert_eq!(set.contains(feature), features.contains(&feature));
+            }
+            prop_assert!(features.len() <= set.count());
+            prop_assert_eq!(set.iter_bits().count(), set.count());
+            let bits: Vec<_> = set.iter_bits().map(Feature::from).collect();
+            prop_assert_eq!(FeaturesSet::from(bits.as_slice()), set);
+        }
+
+        #[test]
+        fn prop_feature_str_roundtrip(feature in feature()) {
+            prop_assert_eq!(feature.to_string().parse::<Feature>(), Ok(feature));
+            prop_assert_eq!(Feature::from(feature.bit()), feature);
+        }
+    }
 }
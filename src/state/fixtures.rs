@@ -0,0 +1,21 @@
+use solana_program::pubkey::Pubkey;
+
+use super::{
+    features::FeaturesSet, record::CountryCode, record::ErRecord, status::ErStatus,
+    version::v0::RecordV0,
+};
+
+/// Creates the latest version record of a new ER node, supporting the given features
+pub fn record(features: FeaturesSet) -> ErRecord {
+    ErRecord::V0(RecordV0 {
+        identity: Pubkey::new_unique(),
+        status: ErStatus::Active,
+        addr: "https://er.magicblock.app".to_string(),
+        block_time_ms: 50,
+        base_fee: 1000,
+        features,
+        load_average: 0,
+        country_code: CountryCode::from_numeric(276).unwrap(),
+    })
+    .into_latest()
+}
@@ -0,0 +1,110 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{consts::MAX_REGION_LEN, error::MdpError};
+
+/// Number of fixed-point units in one degree of latitude or longitude
+pub const MICRODEGREES: i32 = 1_000_000;
+
+/// Mean radius of the Earth in kilometers
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Geographic coordinates of ER node, stored as fixed-point microdegrees
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq)]
+pub struct Coordinates {
+    /// Latitude in microdegrees, within [-90°, 90°]
+    pub lat: i32,
+    /// Longitude in microdegrees, within [-180°, 180°]
+    pub lon: i32,
+}
+
+impl Coordinates {
+    /// Creates coordinates from latitude and longitude in degrees, rounded to microdegrees
+    pub fn from_degrees(lat: f64, lon: f64) -> Result<Self, MdpError> {
+        let to_fixed = |deg: f64| (deg * MICRODEGREES as f64).round();
+        let (lat, lon) = (to_fixed(lat), to_fixed(lon));
+        if lat.is_nan() || lon.is_nan() {
+            return Err(MdpError::InvalidLocation);
+        }
+        // casts saturate, so out of range values fail the validation below
+        let coordinates = Self {
+            lat: lat as i32,
+            lon: lon as i32,
+        };
+        coordinates.validate().map(|_| coordinates)
+    }
+
+    /// Checks whether latitude and longitude are within their ranges
+    pub fn validate(&self) -> Result<(), MdpError> {
+        let lat_valid = (-90 * MICRODEGREES..=90 * MICRODEGREES).contains(&self.lat);
+        let lon_valid = (-180 * MICRODEGREES..=180 * MICRODEGREES).contains(&self.lon);
+        if lat_valid && lon_valid {
+            Ok(())
+        } else {
+            Err(MdpError::InvalidLocation)
+        }
+    }
+
+    /// Returns latitude and longitude in degrees
+    pub fn to_degrees(&self) -> (f64, f64) {
+        let to_deg = |fixed: i32| fixed as f64 / MICRODEGREES as f64;
+        (to_deg(self.lat), to_deg(self.lon))
+    }
+
+    /// Computes great-circle distance in kilometers to the other point, using haversine formula
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let (lat1, lon1) = self.to_degrees();
+        let (lat2, lon2) = other.to_degrees();
+        let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (lon2 - lon1).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
+
+/// Validates cloud region or datacenter identifier of ER node (e.g. "us-east-1"),
+/// it should be a non empty printable ASCII string of at most [`MAX_REGION_LEN`] bytes
+pub fn validate_region(region: &str) -> Result<(), MdpError> {
+    let valid = !region.is_empty()
+        && region.len() <= MAX_REGION_LEN
+        && region.bytes().all(|b| b.is_ascii_graphic());
+    if valid {
+        Ok(())
+    } else {
+        Err(MdpError::InvalidLocation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinates() {
+        let berlin = Coordinates::from_degrees(52.520008, 13.404954).unwrap();
+        assert_eq!(
+            berlin,
+            Coordinates {
+                lat: 52_520_008,
+                lon: 13_404_954
+            }
+        );
+        let new_york = Coordinates::from_degrees(40.712776, -74.005974).unwrap();
+        let distance = berlin.distance_km(&new_york);
+        assert!((distance - 6385.0).abs() < 10.0, "{distance}");
+        assert_eq!(berlin.distance_km(&berlin), 0.0);
+
+        for (lat, lon) in [(90.5, 0.0), (0.0, -180.1), (f64::NAN, 0.0), (0.0, 1e12)] {
+            let result = Coordinates::from_degrees(lat, lon);
+            assert_eq!(result, Err(MdpError::InvalidLocation), "{lat} {lon}");
+        }
+    }
+
+    #[test]
+    fn test_region() {
+        assert_eq!(validate_region("us-east-1"), Ok(()));
+        for region in ["", "us east", &"a".repeat(MAX_REGION_LEN + 1)] {
+            assert_eq!(validate_region(region), Err(MdpError::InvalidLocation));
+        }
+    }
+}
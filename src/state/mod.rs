@@ -1,4 +1,15 @@
+pub mod addr;
+pub mod approval;
+pub mod bond;
+pub mod config;
+pub mod country;
+pub mod endpoint;
 pub mod features;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod location;
+pub mod negotiation;
 pub mod record;
+pub mod registry;
 pub mod status;
 pub mod version;
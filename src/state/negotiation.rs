@@ -0,0 +1,113 @@
+use super::{features::FeaturesSet, record::ErRecord};
+
+/// Feature requirements of the client, used to pick suitable ER nodes
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeaturesQuery {
+    /// Features which ER node has to support, nodes lacking any of them are filtered out
+    pub required: FeaturesSet,
+    /// Features which are nice to have, each supported one improves node's score
+    pub preferred: FeaturesSet,
+}
+
+impl FeaturesQuery {
+    pub fn new(required: FeaturesSet, preferred: FeaturesSet) -> Self {
+        Self {
+            required,
+            preferred,
+        }
+    }
+
+    /// Scores the given featureset against the query, returns None if some of the
+    /// required features are missing, otherwise the number of supported preferred features
+    pub fn score(&self, features: &FeaturesSet) -> Option<usize> {
+        features
+            .is_superset(&self.required)
+            .then(|| features.intersection(&self.preferred).count())
+    }
+
+    /// Scores the given ER record against the query, see [`Self::score`]
+    pub fn score_record(&self, record: &ErRecord) -> Option<usize> {
+        self.score(record.features())
+    }
+
+    /// Filters out the records which don't support all the required features, as well as the
+    /// ones suspended at the given unix timestamp, and sorts the rest by their score, best
+    /// matches first, records with equal score keep their order
+    pub fn rank<'a>(
+        &self,
+        records: impl IntoIterator<Item = &'a ErRecord>,
+        unix_ts: i64,
+    ) -> Vec<(&'a ErRecord, usize)> {
+        let mut ranked: Vec<_> = records
+            .into_iter()
+            .filter(|r| !r.is_suspended(unix_ts))
+            .filter_map(|r| self.score_record(r).map(|score| (r, score)))
+            .collect();
+        ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{features::Feature, fixtures, status::Suspension};
+
+    fn record(features: &[Feature]) -> ErRecord {
+        fixtures::record(features.into())
+    }
+
+    #[test]
+    fn test_features_query_score() {
+        let query = FeaturesQuery::new(
+            [Feature::Randomness].as_slice().into(),
+            [Feature::HighResClock].as_slice().into(),
+        );
+        let both = FeaturesSet::from([Feature::Randomness, Feature::HighResClock].as_slice());
+        assert_eq!(query.score(&both), Some(1));
+        assert_eq!(
+            query.score(&[Feature::Randomness].as_slice().into()),
+            Some(0)
+        );
+        assert_eq!(
+            query.score(&[Feature::HighResClock].as_slice().into()),
+            None
+        );
+        assert_eq!(
+            FeaturesQuery::default().score(&FeaturesSet::default()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_features_query_rank() {
+        let mut records = [
+            record(&[Feature::HighResClock]),
+            record(&[Feature::Randomness]),
+            record(&[Feature::Randomness, Feature::HighResClock]),
+            record(&[Feature::Randomness]),
+            record(&[Feature::Randomness]),
+            record(&[Feature::Randomness, Feature::HighResClock]),
+        ];
+        // the suspension of the former has expired, while the latter is still suspended
+        let suspension = |expires_at| {
+            Some(Suspension {
+                reason: 1,
+                expires_at: Some(expires_at),
+            })
+        };
+        records[4].set_suspension(suspension(100)).unwrap();
+        records[5].set_suspension(suspension(200)).unwrap();
+        let query = FeaturesQuery::new(
+            [Feature::Randomness].as_slice().into(),
+            [Feature::HighResClock].as_slice().into(),
+        );
+        let ranked: Vec<_> = query
+            .rank(&records, 150)
+            .into_iter()
+            .map(|(r, score)| (*r.identity(), score))
+            .collect();
+        let expected = [(2, 1), (1, 0), (3, 0), (4, 0)].map(|(i, s)| (*records[i].identity(), s));
+        assert_eq!(ranked, expected);
+    }
+}
@@ -1,21 +1,85 @@
 use std::borrow::Cow;
 
-use super::{features::FeaturesSet, status::ErStatus, version::v0::RecordV0};
+pub use super::country::CountryCode;
+use super::{
+    addr::validate_addr,
+    endpoint::{primary_rpc, set_primary_rpc, validate_endpoints, Endpoint, EndpointUpdate},
+    features::FeaturesSet,
+    location::{validate_region, Coordinates},
+    status::{ErStatus, Suspension},
+    version::{v0::RecordV0, v1::RecordV1},
+};
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use solana_program::{clock::Clock, pubkey::Pubkey};
 
-use crate::{consts::ER_RECORD_SEED, ID};
+use crate::{
+    consts::{ER_RECORD_DISCRIMINATOR, ER_RECORD_SEED},
+    error::MdpError,
+    ID,
+};
 
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
 #[cfg_attr(not(feature = "entrypoint"), derive(PartialEq, Eq, Clone))]
 pub enum ErRecord {
     V0(RecordV0),
+    V1(RecordV1),
 }
 
 impl ErRecord {
     /// Computes record's PDA for the given ER node
     pub fn pda(&self) -> (Pubkey, u8) {
-        Pubkey::find_program_address(&self.seeds(), &ID)
+        Self::pda_for(self.identity())
+    }
+
+    /// Computes record's PDA for the ER node with the given identity
+    pub fn pda_for(identity: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ER_RECORD_SEED, identity.as_ref()], &ID)
+    }
+
+    /// Converts the record into the latest version, as defined in `state::version`
+    pub fn into_latest(self) -> Self {
+        match self {
+            Self::V0(v) => Self::V1(v.into()),
+            Self::V1(_) => self,
+        }
+    }
+
+    /// Returns true if the record is of the latest version
+    pub fn is_latest(&self) -> bool {
+        matches!(self, Self::V1(_))
+    }
+
+    /// Deserializes the record from account data, accounts created
+    /// before the introduction of discriminator are supported as well
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, MdpError> {
+        let data = data
+            .strip_prefix(ER_RECORD_DISCRIMINATOR.as_slice())
+            .unwrap_or(data);
+        Self::try_from_slice(data).map_err(|_| MdpError::InvalidRecordData)
+    }
+
+    /// Serializes the record into account data, prefixed with discriminator
+    pub fn to_account_data(&self) -> borsh::io::Result<Vec<u8>> {
+        let mut data = ER_RECORD_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Validates the record fields, which can be set by ER node operator, can be used
+    /// to report the existing records, which were registered before the validation
+    pub fn validate(&self) -> Result<(), MdpError> {
+        match self {
+            Self::V0(v) => validate_addr(&v.addr)?,
+            Self::V1(v) => validate_endpoints(&v.endpoints)?,
+        }
+        self.country_code().validate()?;
+        if let Some(coordinates) = self.coordinates() {
+            coordinates.validate()?;
+        }
+        if let Some(region) = self.region() {
+            validate_region(region)?;
+        }
+        Ok(())
     }
 
     /// Returns an array of seeds for the record's PDA derivation
@@ -27,13 +91,62 @@ impl ErRecord {
     pub fn identity(&self) -> &Pubkey {
         match self {
             Self::V0(r) => &r.identity,
+            Self::V1(r) => &r.identity,
+        }
+    }
+
+    /// Returns the key allowed to manage the record, which is the ER identity
+    /// itself for records without dedicated authority (V0 records)
+    pub fn authority(&self) -> &Pubkey {
+        match self {
+            Self::V0(r) => &r.identity,
+            Self::V1(r) => &r.authority,
+        }
+    }
+
+    /// Returns true if the given key is allowed to manage the record,
+    /// i.e. it's either the ER identity or the record's authority
+    pub fn is_authorized(&self, key: &Pubkey) -> bool {
+        key == self.identity() || key == self.authority()
+    }
+
+    /// Returns identity of ER node, which owned the record before the last
+    /// identity rotation, None if the record has never been rotated
+    pub fn previous_identity(&self) -> Option<&Pubkey> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(r) => r.previous_identity.as_ref(),
         }
     }
 
-    /// Returns FQDN address for the given ER node
+    /// Converts the record into the latest version, transferring it to the new ER
+    /// node identity, the authority follows the identity unless it was set explicitly
+    pub fn rotate_identity(self, new_identity: Pubkey) -> Self {
+        let Self::V1(mut v) = self.into_latest() else {
+            unreachable!("latest record version is V1");
+        };
+        if v.authority == v.identity {
+            v.authority = new_identity;
+        }
+        v.previous_identity = Some(v.identity);
+        v.identity = new_identity;
+        Self::V1(v)
+    }
+
+    /// Returns service endpoints of the given ER node, records without
+    /// endpoints expose their address as the only JSON-RPC endpoint
+    pub fn endpoints(&self) -> Cow<'_, [Endpoint]> {
+        match self {
+            Self::V0(v) => Cow::Owned(vec![Endpoint::rpc(v.addr.clone())]),
+            Self::V1(v) => Cow::Borrowed(&v.endpoints),
+        }
+    }
+
+    /// Returns FQDN address for the given ER node, i.e. its primary JSON-RPC endpoint
     pub fn addr(&self) -> &str {
         match self {
             Self::V0(v) => &v.addr,
+            Self::V1(v) => primary_rpc(&v.endpoints).map_or("", |e| &e.addr),
         }
     }
 
@@ -41,6 +154,7 @@ impl ErRecord {
     pub fn base_fee(&self) -> u16 {
         match self {
             Self::V0(v) => v.base_fee,
+            Self::V1(v) => v.base_fee,
         }
     }
 
@@ -48,6 +162,7 @@ impl ErRecord {
     pub fn features(&self) -> &FeaturesSet {
         match self {
             Self::V0(v) => &v.features,
+            Self::V1(v) => &v.features,
         }
     }
 
@@ -55,6 +170,7 @@ impl ErRecord {
     pub fn block_time_ms(&self) -> u16 {
         match self {
             Self::V0(v) => v.block_time_ms,
+            Self::V1(v) => v.block_time_ms,
         }
     }
 
@@ -62,6 +178,7 @@ impl ErRecord {
     pub fn status(&self) -> ErStatus {
         match self {
             Self::V0(v) => v.status,
+            Self::V1(v) => v.status,
         }
     }
 
@@ -69,27 +186,203 @@ impl ErRecord {
     pub fn load_average(&self) -> u32 {
         match self {
             Self::V0(v) => v.load_average,
+            Self::V1(v) => v.load_average,
         }
     }
 
-    /// Returns 3 digit country code of the given ER node
+    /// Returns ISO 3166-1 country code of the given ER node
     pub fn country_code(&self) -> CountryCode {
         match self {
             Self::V0(v) => v.country_code,
+            Self::V1(v) => v.country_code,
+        }
+    }
+
+    /// Returns geographic coordinates of the given ER node, if disclosed
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.coordinates,
+        }
+    }
+
+    /// Returns cloud region or datacenter identifier of the given ER node, if disclosed
+    pub fn region(&self) -> Option<&str> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.region.as_deref(),
+        }
+    }
+
+    /// Computes distance in kilometers between the given ER node and the point,
+    /// None if the coordinates of ER node are unknown
+    pub fn distance_km(&self, to: &Coordinates) -> Option<f64> {
+        self.coordinates().map(|c| c.distance_km(to))
+    }
+
+    /// Returns the suspension imposed on the given ER node by the admin, if any
+    pub fn suspension(&self) -> Option<&Suspension> {
+        match self {
+            Self::V0(_) => None,
+            Self::V1(v) => v.suspension.as_ref(),
         }
     }
 
-    /// Updates the FQDN address in the given ER record
+    /// Returns true if the given ER node is suspended at the given unix timestamp
+    pub fn is_suspended(&self, unix_ts: i64) -> bool {
+        self.suspension().is_some_and(|s| s.is_active(unix_ts))
+    }
+
+    /// Returns true if clients can route to the given ER node, i.e. it's active,
+    /// has been seen within the last `ttl_slots` slots and isn't suspended
+    pub fn is_routable(&self, current_slot: u64, unix_ts: i64, ttl_slots: u64) -> bool {
+        self.status() == ErStatus::Active
+            && self.is_live(current_slot, ttl_slots)
+            && !self.is_suspended(unix_ts)
+    }
+
+    /// Returns unix timestamp of the record registration, 0 if unknown (V0 records)
+    pub fn registered_at(&self) -> i64 {
+        match self {
+            Self::V0(_) => 0,
+            Self::V1(v) => v.registered_at,
+        }
+    }
+
+    /// Returns slot of the last record synchronization or heartbeat, 0 if unknown (V0 records)
+    pub fn last_sync_slot(&self) -> u64 {
+        match self {
+            Self::V0(_) => 0,
+            Self::V1(v) => v.last_sync_slot,
+        }
+    }
+
+    /// Returns unix timestamp of the last record synchronization or heartbeat, 0 if unknown (V0 records)
+    pub fn last_sync_unix_ts(&self) -> i64 {
+        match self {
+            Self::V0(_) => 0,
+            Self::V1(v) => v.last_sync_unix_ts,
+        }
+    }
+
+    /// Returns number of synchronizations applied to the record, 0 for V0 records
+    pub fn seq(&self) -> u64 {
+        match self {
+            Self::V0(_) => 0,
+            Self::V1(v) => v.seq,
+        }
+    }
+
+    /// Returns true if the record has been synced or received a heartbeat within the
+    /// last `ttl_slots` slots, V0 records don't track liveness and are always stale
+    pub fn is_live(&self, current_slot: u64, ttl_slots: u64) -> bool {
+        match self {
+            Self::V0(_) => false,
+            Self::V1(v) => current_slot.saturating_sub(v.last_sync_slot) <= ttl_slots,
+        }
+    }
+
+    /// Resets program managed fields of the record being registered: freshness information
+    /// (using the current clock), sequence number, rotation history and suspension
+    pub fn stamp_registration(&mut self, clock: &Clock) {
+        match self {
+            Self::V0(_) => (),
+            Self::V1(v) => {
+                v.registered_at = clock.unix_timestamp;
+                v.last_sync_slot = clock.slot;
+                v.last_sync_unix_ts = clock.unix_timestamp;
+                v.seq = 0;
+                v.previous_identity = None;
+                v.suspension = None;
+            }
+        }
+    }
+
+    /// Updates freshness information of the record being synchronized, using the current clock
+    pub fn stamp_sync(&mut self, clock: &Clock) {
+        match self {
+            Self::V0(_) => (),
+            Self::V1(v) => {
+                v.last_sync_slot = clock.slot;
+                v.last_sync_unix_ts = clock.unix_timestamp;
+                v.seq = v.seq.saturating_add(1);
+            }
+        }
+    }
+
+    /// Refreshes the liveness of the record, without counting it as synchronization
+    pub fn stamp_heartbeat(&mut self, clock: &Clock) {
+        match self {
+            Self::V0(_) => (),
+            Self::V1(v) => {
+                v.last_sync_slot = clock.slot;
+                v.last_sync_unix_ts = clock.unix_timestamp;
+            }
+        }
+    }
+
+    /// Updates the FQDN address in the given ER record, i.e. its primary JSON-RPC endpoint
     pub fn set_addr(&mut self, addr: String) {
         match self {
             Self::V0(v) => v.addr = addr,
+            Self::V1(v) => set_primary_rpc(&mut v.endpoints, addr),
+        }
+    }
+
+    /// Applies modification of a single endpoint to the given ER record,
+    /// only the records of version 1 can hold multiple endpoints
+    pub fn update_endpoint(&mut self, update: EndpointUpdate) -> Result<(), MdpError> {
+        match self {
+            Self::V0(_) => Err(MdpError::OutdatedRecordVersion),
+            Self::V1(v) => update.apply(&mut v.endpoints),
+        }
+    }
+
+    /// Updates the authority of the given ER record, only the records
+    /// of version 1 can have authority distinct from identity
+    pub fn set_authority(&mut self, authority: Pubkey) -> Result<(), MdpError> {
+        match self {
+            Self::V0(_) => return Err(MdpError::OutdatedRecordVersion),
+            Self::V1(v) => v.authority = authority,
+        }
+        Ok(())
+    }
+
+    /// Updates or clears geographic coordinates of the given ER record,
+    /// only the records of version 1 can hold location
+    pub fn set_coordinates(&mut self, coordinates: Option<Coordinates>) -> Result<(), MdpError> {
+        match self {
+            Self::V0(_) => return Err(MdpError::OutdatedRecordVersion),
+            Self::V1(v) => v.coordinates = coordinates,
+        }
+        Ok(())
+    }
+
+    /// Updates or clears cloud region or datacenter identifier of the given ER record,
+    /// only the records of version 1 can hold location
+    pub fn set_region(&mut self, region: Option<String>) -> Result<(), MdpError> {
+        match self {
+            Self::V0(_) => return Err(MdpError::OutdatedRecordVersion),
+            Self::V1(v) => v.region = region,
+        }
+        Ok(())
+    }
+
+    /// Imposes or lifts the suspension of the given ER record,
+    /// only the records of version 1 can be suspended
+    pub fn set_suspension(&mut self, suspension: Option<Suspension>) -> Result<(), MdpError> {
+        match self {
+            Self::V0(_) => return Err(MdpError::OutdatedRecordVersion),
+            Self::V1(v) => v.suspension = suspension,
         }
+        Ok(())
     }
 
     /// Updates base transaction fee in the given ER record
     pub fn set_base_fee(&mut self, base_fee: u16) {
         match self {
             Self::V0(v) => v.base_fee = base_fee,
+            Self::V1(v) => v.base_fee = base_fee,
         }
     }
 
@@ -97,6 +390,7 @@ impl ErRecord {
     pub fn set_features(&mut self, features: FeaturesSet) {
         match self {
             Self::V0(v) => v.features = features,
+            Self::V1(v) => v.features = features,
         }
     }
 
@@ -104,6 +398,7 @@ impl ErRecord {
     pub fn set_block_time_ms(&mut self, block_time_ms: u16) {
         match self {
             Self::V0(v) => v.block_time_ms = block_time_ms,
+            Self::V1(v) => v.block_time_ms = block_time_ms,
         }
     }
 
@@ -111,6 +406,7 @@ impl ErRecord {
     pub fn set_status(&mut self, status: ErStatus) {
         match self {
             Self::V0(v) => v.status = status,
+            Self::V1(v) => v.status = status,
         }
     }
 
@@ -118,6 +414,7 @@ impl ErRecord {
     pub fn set_load_average(&mut self, load_average: u32) {
         match self {
             Self::V0(v) => v.load_average = load_average,
+            Self::V1(v) => v.load_average = load_average,
         }
     }
 
@@ -125,24 +422,7 @@ impl ErRecord {
     pub fn set_country_code(&mut self, country_code: CountryCode) {
         match self {
             Self::V0(v) => v.country_code = country_code,
+            Self::V1(v) => v.country_code = country_code,
         }
     }
 }
-
-#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq, Eq)]
-pub struct CountryCode([u8; 3]);
-
-impl<S: AsRef<[u8]>> From<S> for CountryCode {
-    fn from(value: S) -> Self {
-        const LEN: usize = std::mem::size_of::<CountryCode>();
-        let mut buf = [0u8; LEN];
-        buf.copy_from_slice(&value.as_ref()[..LEN]);
-        Self(buf)
-    }
-}
-
-impl CountryCode {
-    pub fn as_str(&self) -> Cow<'_, str> {
-        String::from_utf8_lossy(&self.0)
-    }
-}
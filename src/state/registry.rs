@@ -0,0 +1,180 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use super::features::{Feature, FeaturesSet};
+use crate::{
+    consts::{
+        FEATURE_REGISTRY_DISCRIMINATOR, FEATURE_REGISTRY_SEED, MAX_FEATURE_DESCRIPTION_LEN,
+        MAX_FEATURE_NAME_LEN,
+    },
+    error::MdpError,
+    ID,
+};
+
+/// Registry of features, which gives meaning to the bits of [`FeaturesSet`],
+/// so that new ER capabilities can be introduced without program release
+#[derive(Debug, Default, BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq)]
+pub struct FeatureRegistry {
+    /// defined features, ordered by their bit index
+    pub features: Vec<FeatureDefinition>,
+}
+
+/// Description of a single feature bit
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq)]
+pub struct FeatureDefinition {
+    /// index of the bit, which represents the feature in [`FeaturesSet`]
+    pub bit: u8,
+    /// canonical name of the feature, e.g. "high-res-clock"
+    pub name: String,
+    /// human readable description of the feature
+    pub description: String,
+    /// false once the feature has been deprecated, ER nodes should stop advertising it
+    pub active: bool,
+}
+
+impl FeatureDefinition {
+    /// Checks that the name consists of lowercase alphanumerics and dashes and that
+    /// both the name and the description fit into their length limits
+    pub fn validate(&self) -> Result<(), MdpError> {
+        let valid_name = !self.name.is_empty()
+            && self.name.len() <= MAX_FEATURE_NAME_LEN
+            && self
+                .name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+        if valid_name && self.description.len() <= MAX_FEATURE_DESCRIPTION_LEN {
+            Ok(())
+        } else {
+            Err(MdpError::InvalidFeature)
+        }
+    }
+}
+
+impl FeatureRegistry {
+    /// Computes feature registry's PDA
+    pub fn pda() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FEATURE_REGISTRY_SEED], &ID)
+    }
+
+    /// Deserializes the registry from account data
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, MdpError> {
+        data.strip_prefix(FEATURE_REGISTRY_DISCRIMINATOR.as_slice())
+            .and_then(|data| Self::try_from_slice(data).ok())
+            .ok_or(MdpError::InvalidRecordData)
+    }
+
+    /// Serializes the registry into account data, prefixed with discriminator
+    pub fn to_account_data(&self) -> borsh::io::Result<Vec<u8>> {
+        let mut data = FEATURE_REGISTRY_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Returns the definition of the feature with the given bit index
+    pub fn get(&self, bit: u8) -> Option<&FeatureDefinition> {
+        self.features.iter().find(|f| f.bit == bit)
+    }
+
+    /// Returns the set of all the defined features, including the deprecated ones
+    pub fn defined(&self) -> FeaturesSet {
+        self.features.iter().fold(FeaturesSet::default(), |set, f| {
+            set.activate(Feature::from(f.bit))
+        })
+    }
+
+    /// Adds the new feature definition, the feature is always defined as active
+    pub fn define(&mut self, mut feature: FeatureDefinition) -> Result<(), MdpError> {
+        feature.validate()?;
+        if self
+            .features
+            .iter()
+            .any(|f| f.bit == feature.bit || f.name == feature.name)
+        {
+            return Err(MdpError::FeatureAlreadyDefined);
+        }
+        feature.active = true;
+        let index = self.features.partition_point(|f| f.bit < feature.bit);
+        self.features.insert(index, feature);
+        Ok(())
+    }
+
+    /// Marks the feature with the given bit index as deprecated
+    pub fn deprecate(&mut self, bit: u8) -> Result<(), MdpError> {
+        let feature = self
+            .features
+            .iter_mut()
+            .find(|f| f.bit == bit)
+            .ok_or(MdpError::UndefinedFeature)?;
+        feature.active = false;
+        Ok(())
+    }
+
+    /// Checks that every enabled bit of the given featureset is defined in the registry
+    pub fn validate_features(&self, features: &FeaturesSet) -> Result<(), MdpError> {
+        if self.defined().is_superset(features) {
+            Ok(())
+        } else {
+            Err(MdpError::UndefinedFeature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(bit: u8, name: &str) -> FeatureDefinition {
+        FeatureDefinition {
+            bit,
+            name: name.to_string(),
+            description: String::new(),
+            active: false,
+        }
+    }
+
+    #[test]
+    fn test_feature_registry() {
+        let mut registry = FeatureRegistry::default();
+        registry.define(definition(5, "gpu")).unwrap();
+        registry.define(definition(0, "randomness")).unwrap();
+        assert_eq!(registry.features[0].bit, 0);
+        assert!(registry.get(5).unwrap().active);
+
+        assert_eq!(
+            registry.define(definition(5, "tee")),
+            Err(MdpError::FeatureAlreadyDefined)
+        );
+        assert_eq!(
+            registry.define(definition(6, "gpu")),
+            Err(MdpError::FeatureAlreadyDefined)
+        );
+        for name in [
+            "",
+            "High-Res",
+            "tee enclave",
+            &"x".repeat(MAX_FEATURE_NAME_LEN + 1),
+        ] {
+            assert_eq!(
+                registry.define(definition(7, name)),
+                Err(MdpError::InvalidFeature)
+            );
+        }
+
+        registry.deprecate(5).unwrap();
+        assert!(!registry.get(5).unwrap().active);
+        assert_eq!(registry.deprecate(7), Err(MdpError::UndefinedFeature));
+
+        let features = FeaturesSet::default()
+            .activate(Feature::Randomness)
+            .activate(Feature::Unknown(5));
+        assert_eq!(registry.validate_features(&features), Ok(()));
+        let features = features.activate(Feature::HighResClock);
+        assert_eq!(
+            registry.validate_features(&features),
+            Err(MdpError::UndefinedFeature)
+        );
+
+        let data = registry.to_account_data().unwrap();
+        assert_eq!(FeatureRegistry::try_from_account_data(&data), Ok(registry));
+    }
+}
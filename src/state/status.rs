@@ -11,3 +11,20 @@ pub enum ErStatus {
     /// node has been taken offline, e.g. for maintenance
     Offline = 2,
 }
+
+/// Suspension of ER node imposed by the program admin, while in effect the node
+/// should not be routed to by the clients, regardless of its operator set status
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq)]
+pub struct Suspension {
+    /// reason of the suspension, the meaning of the codes is defined by the admin
+    pub reason: u16,
+    /// unix timestamp, after which the suspension is no longer in effect, None if indefinite
+    pub expires_at: Option<i64>,
+}
+
+impl Suspension {
+    /// Returns true if the suspension is in effect at the given unix timestamp
+    pub fn is_active(&self, unix_ts: i64) -> bool {
+        self.expires_at.is_none_or(|expiry| unix_ts < expiry)
+    }
+}
@@ -19,8 +19,7 @@ pub struct RecordV0 {
     /// An average value, which is acts as an indicator
     /// of how loaded the given ER node currently is
     pub load_average: u32,
-    /// 3 digit country code, where ER node is deployed
-    /// IBM spec was used as standard: https://www.ibm.com/docs/en/sia?topic=r-country-region-codes
+    /// ISO 3166-1 numeric or alpha-3 country code, where ER node is deployed
     pub country_code: CountryCode,
     /// Variable length string representing FQDN
     pub addr: String,
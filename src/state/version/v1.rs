@@ -0,0 +1,80 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{
+    endpoint::Endpoint,
+    features::FeaturesSet,
+    location::Coordinates,
+    record::CountryCode,
+    status::{ErStatus, Suspension},
+};
+
+use super::v0::RecordV0;
+
+/// Version 1 of ER domain registry record, extends version 0 with freshness information,
+/// management authority, identity rotation history, typed service endpoints, geographic
+/// location and admin suspension
+#[derive(Debug, BorshSerialize, BorshDeserialize, PartialEq, Eq, Clone)]
+pub struct RecordV1 {
+    /// Identity of ER node (pubkey from its keypair)
+    pub identity: Pubkey,
+    /// Key which is allowed to manage the record on behalf of ER node identity
+    pub authority: Pubkey,
+    /// Identity of ER node, which the record belonged to before the last identity rotation
+    pub previous_identity: Option<Pubkey>,
+    /// Current status of ER node
+    pub status: ErStatus,
+    /// Block time of given ER node in ms
+    pub block_time_ms: u16,
+    /// Base fee charged by ER node per transaction
+    pub base_fee: u16,
+    /// A bitmap of all possible combination of custom features that the ER node supports
+    pub features: FeaturesSet,
+    /// An average value, which is acts as an indicator
+    /// of how loaded the given ER node currently is
+    pub load_average: u32,
+    /// ISO 3166-1 numeric or alpha-3 country code, where ER node is deployed
+    pub country_code: CountryCode,
+    /// Geographic coordinates of ER node, if disclosed
+    pub coordinates: Option<Coordinates>,
+    /// Cloud region or datacenter identifier of ER node (e.g. "us-east-1"), if disclosed
+    pub region: Option<String>,
+    /// Unix timestamp of the moment when record was registered, set by the program
+    pub registered_at: i64,
+    /// Slot of the last record synchronization or heartbeat, set by the program
+    pub last_sync_slot: u64,
+    /// Unix timestamp of the last record synchronization or heartbeat, set by the program
+    pub last_sync_unix_ts: i64,
+    /// Monotonic counter incremented on every record synchronization
+    pub seq: u64,
+    /// Service endpoints exposed by ER node, at least one of them is JSON-RPC endpoint
+    pub endpoints: Vec<Endpoint>,
+    /// Suspension imposed by the program admin, cannot be lifted by ER node operator
+    pub suspension: Option<Suspension>,
+}
+
+impl From<RecordV0> for RecordV1 {
+    /// Version 0 records are managed by ER node identity itself, their address becomes the
+    /// primary JSON-RPC endpoint, while freshness information is unknown and left zeroed
+    fn from(v0: RecordV0) -> Self {
+        Self {
+            identity: v0.identity,
+            authority: v0.identity,
+            previous_identity: None,
+            status: v0.status,
+            block_time_ms: v0.block_time_ms,
+            base_fee: v0.base_fee,
+            features: v0.features,
+            load_average: v0.load_average,
+            country_code: v0.country_code,
+            coordinates: None,
+            region: None,
+            registered_at: 0,
+            last_sync_slot: 0,
+            last_sync_unix_ts: 0,
+            seq: 0,
+            endpoints: vec![Endpoint::rpc(v0.addr)],
+            suspension: None,
+        }
+    }
+}
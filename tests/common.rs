@@ -1,17 +1,27 @@
 use mdp::{
-    instructions::{sync::SyncInstruction, version::v0::SyncRecordV0, Instruction},
+    client,
+    consts::{DEFAULT_REAP_GRACE_SLOTS, DEFAULT_RECORD_TTL_SLOTS},
+    error::MdpError,
+    instructions::{
+        sync::SyncInstruction,
+        version::{v0::SyncRecordV0, v1::SyncRecordV1},
+    },
     state::{
+        config::{ConfigUpdate, ProgramConfig},
+        endpoint::EndpointUpdate,
         features::{Feature, FeaturesSet},
         record::{CountryCode, ErRecord},
-        status::ErStatus,
-        version::v0::RecordV0,
+        registry::FeatureDefinition,
+        status::{ErStatus, Suspension},
+        version::{v0::RecordV0, v1::RecordV1},
     },
 };
-use solana_program_test::{BanksClient, BanksClientError, ProgramTest};
 use solana_account::Account;
-use solana_instruction::{AccountMeta, Instruction as SolanaInstruction};
+use solana_instruction::Instruction as SolanaInstruction;
 use solana_keypair::Keypair;
 use solana_native_token::LAMPORTS_PER_SOL;
+use solana_program::rent::Rent;
+use solana_program_test::{BanksClient, BanksClientError, ProgramTest, ProgramTestContext};
 use solana_pubkey::Pubkey;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
@@ -19,38 +29,125 @@ use solana_transaction::Transaction;
 pub struct TestEnv {
     pub banks: BanksClient,
     pub identity: Keypair,
+    /// funded keypair unrelated to the registered ER node
+    pub intruder: Keypair,
+    /// funded keypair, which can be set as the registered record's authority
+    pub authority: Keypair,
     pub record: ErRecord,
+    /// test context, which allows to manipulate the bank (e.g. warp to slot)
+    pub context: ProgramTestContext,
 }
 
 pub async fn setup() -> TestEnv {
+    setup_with(|_, _| {}).await
+}
+
+/// Same as [`setup`], but allows to modify test environment before it's started
+pub async fn setup_with(prepare: impl FnOnce(&mut ProgramTest, &ErRecord)) -> TestEnv {
     const PROGNAME: &str = "mdp";
     std::env::set_var("SBF_OUT_DIR", "target/deploy/");
     let mut test = ProgramTest::new(PROGNAME, mdp::ID, None);
     let identity = Keypair::new();
+    let intruder = Keypair::new();
+    let authority = Keypair::new();
+
+    for kp in [&identity, &intruder, &authority] {
+        test.add_account(
+            kp.pubkey(),
+            Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+        );
+    }
 
+    // the authority is the upgrade authority of the program, thus it can initialize the config
     test.add_account(
-        identity.pubkey(),
-        Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+        ProgramConfig::program_data_address(),
+        program_data(&authority.pubkey()),
     );
 
+    let record = record(&identity.pubkey());
+    prepare(&mut test, &record);
+    let context = test.start_with_context().await;
+    let banks = context.banks_client.clone();
+
+    TestEnv {
+        banks,
+        identity,
+        intruder,
+        authority,
+        record,
+        context,
+    }
+}
+
+/// Creates program data account of the upgradeable program, without the program binary
+fn program_data(upgrade_authority: &Pubkey) -> Account {
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(upgrade_authority.as_ref());
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: solana_sdk_ids::bpf_loader_upgradeable::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn record(identity: &Pubkey) -> ErRecord {
     let features = FeaturesSet::default().activate(Feature::Randomness);
-    let record = ErRecord::V0(RecordV0 {
-        identity: identity.pubkey(),
+    ErRecord::V0(RecordV0 {
+        identity: *identity,
         status: ErStatus::Active,
         addr: "https://241.132.2.41:9324/".to_string(),
         block_time_ms: 50,
         base_fee: 1000,
         features,
         load_average: 1_000_000,
-        country_code: CountryCode::from("083"),
-    });
-    let (banks, _, _) = test.start().await;
+        country_code: CountryCode::try_from("276").unwrap(),
+    })
+}
 
-    TestEnv {
-        banks,
-        identity,
-        record,
-    }
+pub fn record_v1(identity: &Pubkey) -> ErRecord {
+    let ErRecord::V0(v0) = record(identity) else {
+        unreachable!()
+    };
+    ErRecord::V1(RecordV1 {
+        // bogus values, which should be overwritten by the program
+        registered_at: 42,
+        last_sync_slot: 42,
+        last_sync_unix_ts: 42,
+        seq: 42,
+        ..v0.into()
+    })
+}
+
+pub fn record_with_authority(identity: &Pubkey, authority: &Pubkey) -> ErRecord {
+    let mut record = record_v1(identity);
+    record.set_authority(*authority).unwrap();
+    record
+}
+
+/// Creates record account the way it was stored before the introduction of discriminator
+pub fn add_legacy_record(test: &mut ProgramTest, record: &ErRecord) {
+    add_record_data(test, record, borsh::to_vec(record).unwrap());
+}
+
+/// Creates record account in the given version, the way older program versions stored it
+pub fn add_record(test: &mut ProgramTest, record: &ErRecord) {
+    add_record_data(test, record, record.to_account_data().unwrap());
+}
+
+fn add_record_data(test: &mut ProgramTest, record: &ErRecord, data: Vec<u8>) {
+    let lamports = Rent::default().minimum_balance(data.len());
+    let account = Account {
+        lamports,
+        data,
+        owner: mdp::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    test.add_account(record.pda().0, account);
 }
 
 pub async fn register(
@@ -58,20 +155,10 @@ pub async fn register(
     record: ErRecord,
     identity: &Keypair,
 ) -> Result<(), BanksClientError> {
-    let pda = record.pda().0;
-    let ix = Instruction::Register(record);
-    let ix = SolanaInstruction::new_with_borsh(
-        mdp::ID,
-        &ix,
-        vec![
-            AccountMeta::new(identity.pubkey(), true),
-            AccountMeta::new(pda, false),
-            AccountMeta::new_readonly(Pubkey::default(), false),
-        ],
-    );
-    let hash = banks.get_latest_blockhash().await.unwrap();
-    let tx = Transaction::new_signed_with_payer(&[ix], Some(&identity.pubkey()), &[identity], hash);
-    banks.process_transaction(tx).await
+    let mut ix = client::register_ix(record);
+    // allow signing with the key which is not the record's identity
+    ix.accounts[0].pubkey = identity.pubkey();
+    send(banks, ix, identity, &[identity]).await
 }
 
 pub async fn unregister(
@@ -79,20 +166,20 @@ pub async fn unregister(
     identity: &Keypair,
     pda: Pubkey,
 ) -> Result<(), BanksClientError> {
-    let ix = Instruction::Unregister(identity.pubkey());
-    let ix = SolanaInstruction::new_with_borsh(
-        mdp::ID,
-        &ix,
-        vec![
-            AccountMeta::new(identity.pubkey(), true),
-            AccountMeta::new(pda, false),
-            AccountMeta::new_readonly(Pubkey::default(), false),
-        ],
-    );
-    let hash = banks.get_latest_blockhash().await.unwrap();
-    let tx =
-        Transaction::new_signed_with_payer(&[ix], Some(&identity.pubkey()), &[&identity], hash);
-    banks.process_transaction(tx).await
+    let mut ix = client::unregister_ix(identity.pubkey());
+    // allow targeting records which don't belong to the signer
+    ix.accounts[1].pubkey = pda;
+    send(banks, ix, identity, &[identity]).await
+}
+
+/// Same as [`unregister`], but the transaction is signed by the record's authority
+pub async fn authority_unregister(
+    banks: &mut BanksClient,
+    authority: &Keypair,
+    identity: Pubkey,
+) -> Result<(), BanksClientError> {
+    let ix = client::authority_unregister_ix(authority.pubkey(), identity);
+    send(banks, ix, authority, &[authority]).await
 }
 
 pub async fn sync(
@@ -100,8 +187,7 @@ pub async fn sync(
     identity: &Keypair,
     record: ErRecord,
 ) -> Result<(), BanksClientError> {
-    let pda = record.pda().0;
-    let ix = Instruction::Sync(SyncInstruction::V0(SyncRecordV0 {
+    let mut ix = client::sync_ix(SyncInstruction::V0(SyncRecordV0 {
         identity: *record.identity(),
         status: Some(record.status()),
         addr: Some(record.addr().to_owned()),
@@ -111,21 +197,205 @@ pub async fn sync(
         load_average: Some(record.load_average()),
         country_code: Some(record.country_code()),
     }));
-    let ix = SolanaInstruction::new_with_borsh(
-        mdp::ID,
-        &ix,
-        vec![
-            AccountMeta::new(identity.pubkey(), true),
-            AccountMeta::new(pda, false),
-            AccountMeta::new_readonly(Pubkey::default(), false),
-        ],
+    // allow signing with the key which is not the record's identity
+    ix.accounts[0].pubkey = identity.pubkey();
+    send(banks, ix, identity, &[identity]).await
+}
+
+/// Same as [`sync`], but the transaction is signed by the record's authority
+pub async fn authority_sync(
+    banks: &mut BanksClient,
+    authority: &Keypair,
+    record: ErRecord,
+) -> Result<(), BanksClientError> {
+    let ix = client::authority_sync_ix(
+        authority.pubkey(),
+        SyncInstruction::V0(SyncRecordV0 {
+            identity: *record.identity(),
+            status: Some(record.status()),
+            addr: Some(record.addr().to_owned()),
+            block_time_ms: Some(record.block_time_ms()),
+            base_fee: Some(record.base_fee()),
+            features: Some(record.features().clone()),
+            load_average: Some(record.load_average()),
+            country_code: Some(record.country_code()),
+        }),
     );
+    send(banks, ix, authority, &[authority]).await
+}
+
+/// Same as [`sync`], but the update is applied only if record has the expected sequence number
+pub async fn sync_cas(
+    banks: &mut BanksClient,
+    identity: &Keypair,
+    record: ErRecord,
+    expected_seq: u64,
+) -> Result<(), BanksClientError> {
+    let ix = client::sync_ix(SyncInstruction::V1(SyncRecordV1 {
+        identity: *record.identity(),
+        expected_seq: Some(expected_seq),
+        authority: None,
+        status: Some(record.status()),
+        addr: Some(record.addr().to_owned()),
+        block_time_ms: Some(record.block_time_ms()),
+        base_fee: Some(record.base_fee()),
+        features: Some(record.features().clone()),
+        load_average: Some(record.load_average()),
+        country_code: Some(record.country_code()),
+        coordinates: None,
+        region: None,
+        endpoints: Vec::new(),
+    }));
+    send(banks, ix, identity, &[identity]).await
+}
+
+/// Applies the endpoint modifications to the record of the given ER node
+pub async fn sync_endpoints(
+    banks: &mut BanksClient,
+    identity: &Keypair,
+    endpoints: Vec<EndpointUpdate>,
+) -> Result<(), BanksClientError> {
+    let ix = client::sync_ix(SyncInstruction::V1(SyncRecordV1 {
+        identity: identity.pubkey(),
+        expected_seq: None,
+        authority: None,
+        status: None,
+        addr: None,
+        block_time_ms: None,
+        base_fee: None,
+        features: None,
+        load_average: None,
+        country_code: None,
+        coordinates: None,
+        region: None,
+        endpoints,
+    }));
+    send(banks, ix, identity, &[identity]).await
+}
+
+pub async fn migrate(banks: &mut BanksClient, identity: &Keypair) -> Result<(), BanksClientError> {
+    let ix = client::migrate_ix(identity.pubkey());
+    send(banks, ix, identity, &[identity]).await
+}
+
+pub async fn heartbeat(
+    banks: &mut BanksClient,
+    identity: &Keypair,
+) -> Result<(), BanksClientError> {
+    let ix = client::heartbeat_ix(identity.pubkey());
+    send(banks, ix, identity, &[identity]).await
+}
+
+pub async fn rotate_identity(
+    banks: &mut BanksClient,
+    identity: &Keypair,
+    new_identity: &Keypair,
+) -> Result<(), BanksClientError> {
+    let ix = client::rotate_identity_ix(identity.pubkey(), new_identity.pubkey());
+    send(banks, ix, identity, &[identity, new_identity]).await
+}
+
+pub async fn reap(
+    banks: &mut BanksClient,
+    caller: &Keypair,
+    identity: Pubkey,
+) -> Result<(), BanksClientError> {
+    let ix = client::reap_ix(caller.pubkey(), identity);
+    send(banks, ix, caller, &[caller]).await
+}
+
+/// Defines the feature in the registry, creating the registry if it doesn't exist yet
+pub async fn define_feature(
+    banks: &mut BanksClient,
+    admin: &Keypair,
+    bit: u8,
+    name: &str,
+) -> Result<(), BanksClientError> {
+    let feature = FeatureDefinition {
+        bit,
+        name: name.to_string(),
+        description: format!("{name} support"),
+        active: true,
+    };
+    let ix = client::define_feature_ix(admin.pubkey(), feature);
+    send(banks, ix, admin, &[admin]).await
+}
+
+pub async fn init_config(banks: &mut BanksClient, admin: &Keypair) -> Result<(), BanksClientError> {
+    let ix = client::init_config_ix(admin.pubkey());
+    send(banks, ix, admin, &[admin]).await
+}
+
+pub async fn update_config(
+    banks: &mut BanksClient,
+    admin: &Keypair,
+    update: ConfigUpdate,
+) -> Result<(), BanksClientError> {
+    let ix = client::update_config_ix(admin.pubkey(), update);
+    send(banks, ix, admin, &[admin]).await
+}
+
+pub async fn grant_approval(
+    banks: &mut BanksClient,
+    admin: &Keypair,
+    identity: Pubkey,
+) -> Result<(), BanksClientError> {
+    let ix = client::grant_approval_ix(admin.pubkey(), identity);
+    send(banks, ix, admin, &[admin]).await
+}
+
+pub async fn suspend(
+    banks: &mut BanksClient,
+    admin: &Keypair,
+    identity: Pubkey,
+    suspension: Suspension,
+) -> Result<(), BanksClientError> {
+    let ix = client::suspend_ix(admin.pubkey(), identity, suspension);
+    send(banks, ix, admin, &[admin]).await
+}
+
+pub async fn deposit_bond(
+    banks: &mut BanksClient,
+    funder: &Keypair,
+    identity: Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let ix = client::deposit_bond_ix(funder.pubkey(), identity, amount);
+    send(banks, ix, funder, &[funder]).await
+}
+
+/// Warps the bank past the liveness and grace period of the record stored at the given PDA
+pub async fn expire(context: &mut ProgramTestContext, pda: Pubkey) {
+    let acc = context
+        .banks_client
+        .get_account(pda)
+        .await
+        .unwrap()
+        .unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    let slot = record.last_sync_slot() + DEFAULT_RECORD_TTL_SLOTS + DEFAULT_REAP_GRACE_SLOTS + 1;
+    context.warp_to_slot(slot).unwrap();
+}
+
+pub async fn send(
+    banks: &mut BanksClient,
+    ix: SolanaInstruction,
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
     let hash = banks.get_latest_blockhash().await.unwrap();
-    let tx =
-        Transaction::new_signed_with_payer(&[ix], Some(&identity.pubkey()), &[&identity], hash);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), signers, hash);
     banks.process_transaction(tx).await
 }
 
+/// Extracts program specific error from the failed transaction result
+pub fn mdp_error(result: Result<(), BanksClientError>) -> Option<MdpError> {
+    match result {
+        Err(BanksClientError::TransactionError(error)) => MdpError::from_transaction_error(&error),
+        _ => None,
+    }
+}
+
 #[macro_export]
 macro_rules! assert_ok {
     ($result: ident, $errmsg: expr) => {{
@@ -133,3 +403,10 @@ macro_rules! assert_ok {
         $result.unwrap()
     }};
 }
+
+#[macro_export]
+macro_rules! assert_mdp_err {
+    ($result: expr, $expected: expr) => {{
+        assert_eq!(common::mdp_error($result), Some($expected));
+    }};
+}
@@ -0,0 +1,127 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    state::{approval::Approval, config::ConfigUpdate},
+};
+use solana_keypair::Keypair;
+use solana_program::program_error::ProgramError;
+use solana_program_test::{BanksClient, BanksClientError};
+use solana_signer::Signer;
+use solana_system_interface::instruction::transfer;
+use solana_transaction_error::TransactionError;
+
+pub mod common;
+
+async fn setup_permissioned(banks: &mut BanksClient, admin: &Keypair) {
+    let result = common::init_config(banks, admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let update = ConfigUpdate {
+        permissioned: Some(true),
+        ..Default::default()
+    };
+    let result = common::update_config(banks, admin, update).await;
+    assert_ok!(result, "error updating config {}");
+}
+
+#[tokio::test]
+async fn test_permissioned_registration() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority: admin,
+        ..
+    } = common::setup().await;
+    setup_permissioned(&mut banks, &admin).await;
+
+    let mut record = common::record(&identity.pubkey());
+    // differs from the registration below, so that the transaction isn't deduplicated
+    record.set_block_time_ms(100);
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_mdp_err!(result, MdpError::NotApproved);
+
+    let result = common::grant_approval(&mut banks, &intruder, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    // funding the approval PDA beforehand doesn't prevent the approval
+    let pda = Approval::pda_for(&identity.pubkey()).0;
+    let ix = transfer(&intruder.pubkey(), &pda, 1_000_000);
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_ok!(result, "error funding approval PDA {}");
+    let result = common::grant_approval(&mut banks, &admin, identity.pubkey()).await;
+    assert_ok!(result, "error granting approval {}");
+    // different fee payer, so that the transaction isn't deduplicated
+    let ix = client::grant_approval_ix(admin.pubkey(), identity.pubkey());
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder, &admin]).await;
+    assert_mdp_err!(result, MdpError::AlreadyApproved);
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let approval = Approval::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(approval.identity, identity.pubkey());
+    assert_eq!(approval.granted_by, admin.pubkey());
+
+    let record = common::record(&identity.pubkey());
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    // the unapproved identity can't be sneaked in via rotation either
+    let result = common::rotate_identity(&mut banks, &identity, &intruder).await;
+    assert_mdp_err!(result, MdpError::NotApproved);
+}
+
+#[tokio::test]
+async fn test_permissioned_registration_without_config() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        record,
+        ..
+    } = common::setup().await;
+    setup_permissioned(&mut banks, &admin).await;
+
+    // leaving out the policy accounts doesn't bypass the approval
+    let mut ix = client::register_ix(record);
+    ix.accounts.truncate(3);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    let Err(BanksClientError::TransactionError(error)) = result else {
+        panic!("registration without the program config has succeeded");
+    };
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, u64::from(ProgramError::NotEnoughAccountKeys).into())
+    );
+}
+
+#[tokio::test]
+async fn test_approval_revocation() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        ..
+    } = common::setup().await;
+    setup_permissioned(&mut banks, &admin).await;
+
+    let result = common::grant_approval(&mut banks, &admin, identity.pubkey()).await;
+    assert_ok!(result, "error granting approval {}");
+    let admin_balance = banks.get_balance(admin.pubkey()).await.unwrap();
+    let pda = Approval::pda_for(&identity.pubkey()).0;
+    let rent = banks.get_balance(pda).await.unwrap();
+
+    let ix = client::revoke_approval_ix(admin.pubkey(), identity.pubkey());
+    let result = common::send(&mut banks, ix, &admin, &[&admin]).await;
+    assert_ok!(result, "error revoking approval {}");
+    assert!(banks.get_account(pda).await.unwrap().is_none());
+    let balance = banks.get_balance(admin.pubkey()).await.unwrap();
+    assert_eq!(balance, admin_balance + rent - 5_000);
+
+    // different fee payer, so that the transaction isn't deduplicated
+    let ix = client::revoke_approval_ix(admin.pubkey(), identity.pubkey());
+    let result = common::send(&mut banks, ix, &identity, &[&identity, &admin]).await;
+    assert_mdp_err!(result, MdpError::NotApproved);
+
+    let record = common::record(&identity.pubkey());
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_mdp_err!(result, MdpError::NotApproved);
+}
@@ -0,0 +1,136 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    state::{bond::Bond, config::ConfigUpdate},
+};
+use solana_native_token::LAMPORTS_PER_SOL;
+use solana_program::program_error::ProgramError;
+use solana_program_test::BanksClientError;
+use solana_signer::Signer;
+use solana_system_interface::instruction::transfer;
+use solana_transaction_error::TransactionError;
+
+pub mod common;
+
+const MIN_BOND: u64 = LAMPORTS_PER_SOL / 10;
+
+#[tokio::test]
+async fn test_bonded_registration() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        intruder: funder,
+        record,
+        mut context,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+    let vault = Bond::pda_for(&identity.pubkey()).0;
+
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let update = ConfigUpdate {
+        min_bond_lamports: Some(MIN_BOND),
+        bond_cooldown_slots: Some(10),
+        ..Default::default()
+    };
+    let result = common::update_config(&mut banks, &admin, update).await;
+    assert_ok!(result, "error updating config {}");
+
+    let mut unbonded = common::record(&identity.pubkey());
+    // differs from the registration below, so that the transaction isn't deduplicated
+    unbonded.set_block_time_ms(100);
+    let result = common::register(&mut banks, unbonded, &identity).await;
+    assert_mdp_err!(result, MdpError::InsufficientBond);
+
+    // funding the vault beforehand doesn't prevent the deposits
+    let ix = transfer(&funder.pubkey(), &vault, 1_000_000);
+    let result = common::send(&mut banks, ix, &funder, &[&funder]).await;
+    assert_ok!(result, "error funding bond vault {}");
+    let result = common::deposit_bond(&mut banks, &funder, identity.pubkey(), 0).await;
+    assert_mdp_err!(result, MdpError::InvalidBondAmount);
+    let result = common::deposit_bond(&mut banks, &funder, identity.pubkey(), MIN_BOND / 2).await;
+    assert_ok!(result, "error depositing bond {}");
+    let result = common::deposit_bond(&mut banks, &identity, identity.pubkey(), MIN_BOND / 2).await;
+    assert_ok!(result, "error depositing bond {}");
+    let acc = banks.get_account(vault).await.unwrap().unwrap();
+    let bond = Bond::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(bond.bonded(), MIN_BOND);
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let ix = client::unlock_bond_ix(identity.pubkey());
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::BondLocked);
+
+    let result = common::unregister(&mut banks, &identity, pda).await;
+    assert_ok!(result, "error processing unregister transaction {}");
+    // neither does funding the closed record PDA keep the bond locked
+    let ix = transfer(&funder.pubkey(), &pda, 1_000_000);
+    let result = common::send(&mut banks, ix, &funder, &[&funder]).await;
+    assert_ok!(result, "error funding record PDA {}");
+    // different fee payer, so that the transaction isn't deduplicated
+    let ix = client::unlock_bond_ix(identity.pubkey());
+    let result = common::send(&mut banks, ix, &funder, &[&funder, &identity]).await;
+    assert_ok!(result, "error unlocking bond {}");
+
+    let ix = client::withdraw_bond_ix(identity.pubkey());
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::BondLocked);
+
+    // nobody but the identity can restart the cooldown by topping up the bond
+    let result = common::deposit_bond(&mut banks, &funder, identity.pubkey(), 1).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+    let acc = banks.get_account(vault).await.unwrap().unwrap();
+    let bond = Bond::try_from_account_data(&acc.data).unwrap();
+    assert!(bond.unlock_slot.is_some(), "bond has been locked again");
+
+    let slot = banks.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + 20).unwrap();
+    let mut banks = context.banks_client.clone();
+
+    let balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    let vault_balance = banks.get_balance(vault).await.unwrap();
+    let ix = client::withdraw_bond_ix(identity.pubkey());
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_ok!(result, "error withdrawing bond {}");
+
+    assert!(banks.get_account(vault).await.unwrap().is_none());
+    let withdrawn = banks.get_balance(identity.pubkey()).await.unwrap();
+    assert_eq!(withdrawn, balance + vault_balance - 5_000);
+}
+
+#[tokio::test]
+async fn test_bonded_registration_without_config() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let update = ConfigUpdate {
+        min_bond_lamports: Some(MIN_BOND),
+        ..Default::default()
+    };
+    let result = common::update_config(&mut banks, &admin, update).await;
+    assert_ok!(result, "error updating config {}");
+
+    // leaving out the policy accounts doesn't bypass the bond requirement
+    let mut ix = client::register_ix(record);
+    ix.accounts.truncate(3);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    let Err(BanksClientError::TransactionError(error)) = result else {
+        panic!("registration without the program config has succeeded");
+    };
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, u64::from(ProgramError::NotEnoughAccountKeys).into())
+    );
+}
@@ -0,0 +1,119 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    state::config::{ConfigUpdate, ProgramConfig},
+};
+use solana_program_test::BanksClient;
+use solana_signer::Signer;
+use solana_system_interface::instruction::transfer;
+
+pub mod common;
+
+async fn config(banks: &mut BanksClient) -> ProgramConfig {
+    let acc = banks
+        .get_account(ProgramConfig::pda().0)
+        .await
+        .unwrap()
+        .unwrap();
+    ProgramConfig::try_from_account_data(&acc.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_config_admin_transfer() {
+    let TestEnv {
+        mut banks,
+        authority: admin,
+        identity: new_admin,
+        intruder,
+        ..
+    } = common::setup().await;
+
+    // only the upgrade authority can initialize the config, even if it's been funded by someone else
+    let ix = transfer(&intruder.pubkey(), &ProgramConfig::pda().0, 1_000_000);
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_ok!(result, "error funding config PDA {}");
+    let result = common::init_config(&mut banks, &intruder).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let ix = client::init_config_ix(admin.pubkey());
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder, &admin]).await;
+    assert_mdp_err!(result, MdpError::ConfigAlreadyInitialized);
+    let initialized = config(&mut banks).await;
+    let slot = initialized.initialized_slot.unwrap();
+    assert_eq!(initialized, ProgramConfig::new(admin.pubkey(), slot));
+
+    let update = ConfigUpdate {
+        pending_admin: Some(Some(new_admin.pubkey())),
+        ..Default::default()
+    };
+    let result = common::update_config(&mut banks, &intruder, update.clone()).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    let result = common::update_config(&mut banks, &admin, update).await;
+    assert_ok!(result, "error updating config {}");
+
+    let ix = client::accept_config_admin_ix(intruder.pubkey());
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    let ix = client::accept_config_admin_ix(new_admin.pubkey());
+    let result = common::send(&mut banks, ix, &new_admin, &[&new_admin]).await;
+    assert_ok!(result, "error accepting admin role {}");
+
+    let config = config(&mut banks).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    let update = ConfigUpdate {
+        record_ttl_slots: Some(100),
+        ..Default::default()
+    };
+    let result = common::update_config(&mut banks, &admin, update).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+}
+
+#[tokio::test]
+async fn test_config_reap_policy() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority: admin,
+        mut context,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let update = ConfigUpdate {
+        record_ttl_slots: Some(0),
+        ..Default::default()
+    };
+    let result = common::update_config(&mut banks, &admin, update).await;
+    assert_mdp_err!(result, MdpError::InvalidConfig);
+    let update = ConfigUpdate {
+        record_ttl_slots: Some(10),
+        reap_grace_slots: Some(0),
+        reap_bounty_bps: Some(10_000),
+        ..Default::default()
+    };
+    let result = common::update_config(&mut banks, &admin, update).await;
+    assert_ok!(result, "error updating config {}");
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let slot = banks.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + 20).unwrap();
+    let mut banks = context.banks_client.clone();
+
+    let identity_balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_ok!(result, "error processing reap transaction {}");
+
+    // the whole rent goes to the caller
+    let balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    assert_eq!(balance, identity_balance);
+    assert_eq!(banks.get_balance(pda).await.unwrap(), 0);
+}
@@ -0,0 +1,97 @@
+use common::TestEnv;
+use mdp::{
+    error::MdpError,
+    state::{
+        endpoint::{Endpoint, EndpointUpdate, ServiceKind},
+        record::ErRecord,
+    },
+};
+
+pub mod common;
+
+fn endpoint(kind: ServiceKind, priority: u16, addr: &str) -> Endpoint {
+    Endpoint {
+        kind,
+        priority,
+        weight: 10,
+        addr: addr.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_sync_endpoints() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+    let addr = record.addr().to_owned();
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let result = common::migrate(&mut banks, &identity).await;
+    assert_ok!(result, "error processing migrate transaction {}");
+
+    let ws = endpoint(ServiceKind::Ws, 0, "wss://ws.er.app:9325");
+    let grpc = endpoint(ServiceKind::Grpc, 0, "https://grpc.er.app:9000");
+    let rpc = endpoint(ServiceKind::Rpc, 0, "https://rpc.er.app");
+    let updates = vec![
+        EndpointUpdate::Add(ws.clone()),
+        EndpointUpdate::Add(grpc.clone()),
+        EndpointUpdate::Replace {
+            kind: ServiceKind::Rpc,
+            addr,
+            endpoint: rpc.clone(),
+        },
+        EndpointUpdate::Remove {
+            kind: ServiceKind::Grpc,
+            addr: grpc.addr,
+        },
+    ];
+    let result = common::sync_endpoints(&mut banks, &identity, updates).await;
+    assert_ok!(result, "error processing sync endpoints transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.endpoints().as_ref(), [rpc.clone(), ws]);
+    assert_eq!(record.addr(), rpc.addr);
+
+    let updates = vec![EndpointUpdate::Remove {
+        kind: ServiceKind::Rpc,
+        addr: rpc.addr.clone(),
+    }];
+    let result = common::sync_endpoints(&mut banks, &identity, updates).await;
+    assert_mdp_err!(result, MdpError::InvalidEndpoint);
+
+    let updates = vec![EndpointUpdate::Remove {
+        kind: ServiceKind::Metrics,
+        addr: rpc.addr,
+    }];
+    let result = common::sync_endpoints(&mut banks, &identity, updates).await;
+    assert_mdp_err!(result, MdpError::EndpointNotFound);
+}
+
+#[tokio::test]
+async fn test_sync_endpoints_legacy_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup_with(common::add_record).await;
+    let pda = record.pda().0;
+
+    let ws = endpoint(ServiceKind::Ws, 0, "wss://ws.er.app:9325");
+    let updates = vec![EndpointUpdate::Add(ws.clone())];
+    let result = common::sync_endpoints(&mut banks, &identity, updates).await;
+    assert_ok!(result, "error processing sync endpoints transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(
+        *record.endpoints(),
+        [Endpoint::rpc(record.addr().to_owned()), ws]
+    );
+}
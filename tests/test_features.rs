@@ -0,0 +1,166 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    instructions::{sync::SyncInstruction, version::v0::SyncRecordV0},
+    state::{
+        config::ConfigUpdate,
+        features::{Feature, FeaturesSet},
+        registry::FeatureRegistry,
+    },
+};
+use solana_keypair::Keypair;
+use solana_program::program_error::ProgramError;
+use solana_program_test::{BanksClient, BanksClientError};
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+pub mod common;
+
+async fn require_defined_features(banks: &mut BanksClient, admin: &Keypair) {
+    let result = common::init_config(banks, admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let update = ConfigUpdate {
+        require_defined_features: Some(true),
+        ..Default::default()
+    };
+    let result = common::update_config(banks, admin, update).await;
+    assert_ok!(result, "error updating config {}");
+}
+
+#[tokio::test]
+async fn test_feature_registry() {
+    let TestEnv {
+        mut banks,
+        authority: admin,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+
+    // nobody can create the registry before the config has been initialized
+    let result = common::define_feature(&mut banks, &intruder, 0, "randomness").await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let result = common::define_feature(&mut banks, &admin, 0, "randomness").await;
+    assert_ok!(result, "error creating feature registry {}");
+    let result = common::define_feature(&mut banks, &admin, 7, "gpu").await;
+    assert_ok!(result, "error defining feature {}");
+
+    let result = common::define_feature(&mut banks, &intruder, 8, "tee").await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    let result = common::define_feature(&mut banks, &admin, 7, "tee").await;
+    assert_mdp_err!(result, MdpError::FeatureAlreadyDefined);
+
+    let ix = client::deprecate_feature_ix(intruder.pubkey(), 7);
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    let ix = client::deprecate_feature_ix(admin.pubkey(), 7);
+    let result = common::send(&mut banks, ix, &admin, &[&admin]).await;
+    assert_ok!(result, "error deprecating feature {}");
+
+    let acc = banks
+        .get_account(FeatureRegistry::pda().0)
+        .await
+        .unwrap()
+        .unwrap();
+    let registry = FeatureRegistry::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(registry.features.len(), 2);
+
+    let features = record
+        .features()
+        .clone()
+        .activate(Feature::HighResClock)
+        .activate(Feature::Unknown(7));
+    assert_eq!(
+        client::render_features(&registry, &features),
+        "randomness, high-res-clock, gpu (deprecated)"
+    );
+}
+
+#[tokio::test]
+async fn test_registration_undefined_feature() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        mut record,
+        ..
+    } = common::setup().await;
+    require_defined_features(&mut banks, &admin).await;
+
+    let result = common::define_feature(&mut banks, &admin, 1, "high-res-clock").await;
+    assert_ok!(result, "error creating feature registry {}");
+
+    let result = common::register(&mut banks, common::record(&identity.pubkey()), &identity).await;
+    assert_mdp_err!(result, MdpError::UndefinedFeature);
+
+    // the registry can't be left out, once the policy requires it
+    record.set_features(FeaturesSet::default().activate(Feature::HighResClock));
+    let mut ix = client::register_ix(record);
+    let registry = ix.accounts.pop().unwrap();
+    let result = common::send(&mut banks, ix.clone(), &identity, &[&identity]).await;
+    let Err(BanksClientError::TransactionError(error)) = result else {
+        panic!("registration without the feature registry has succeeded");
+    };
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, u64::from(ProgramError::NotEnoughAccountKeys).into())
+    );
+
+    ix.accounts.push(registry);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_ok!(result, "error processing register transaction {}");
+}
+
+#[tokio::test]
+async fn test_sync_undefined_feature() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    require_defined_features(&mut banks, &admin).await;
+    let result = common::define_feature(&mut banks, &admin, 0, "randomness").await;
+    assert_ok!(result, "error creating feature registry {}");
+
+    let sync = |features: FeaturesSet| {
+        client::sync_ix(SyncInstruction::V0(SyncRecordV0 {
+            identity: identity.pubkey(),
+            features: Some(features),
+            status: None,
+            addr: None,
+            block_time_ms: None,
+            base_fee: None,
+            load_average: None,
+            country_code: None,
+        }))
+    };
+
+    let ix = sync(FeaturesSet::default().activate(Feature::HighResClock));
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::UndefinedFeature);
+
+    // leaving out the policy accounts doesn't bypass the check
+    let mut ix = sync(FeaturesSet::default().activate(Feature::HighResClock));
+    ix.accounts.truncate(3);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    let Err(BanksClientError::TransactionError(error)) = result else {
+        panic!("sync without the program config has succeeded");
+    };
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(0, u64::from(ProgramError::NotEnoughAccountKeys).into())
+    );
+
+    let ix = sync(FeaturesSet::default());
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_ok!(result, "error processing sync transaction {}");
+}
@@ -0,0 +1,74 @@
+use common::TestEnv;
+use mdp::{consts::DEFAULT_RECORD_TTL_SLOTS, error::MdpError, state::record::ErRecord};
+use solana_signer::Signer;
+
+pub mod common;
+
+#[tokio::test]
+async fn test_heartbeat() {
+    let TestEnv {
+        mut banks,
+        identity,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let registered = ErRecord::try_from_account_data(&acc.data).unwrap();
+
+    let result = common::heartbeat(&mut banks, &identity).await;
+    assert_ok!(result, "error processing heartbeat transaction {}");
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(result, "error querying registration PDA after heartbeat {}");
+    let acc = acc.unwrap();
+    assert_eq!(acc.data.len(), registered.to_account_data().unwrap().len());
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    let slot = banks.get_root_slot().await.unwrap();
+    assert!(record.last_sync_slot() >= registered.last_sync_slot());
+    assert_eq!(record.seq(), registered.seq(), "heartbeat isn't a sync");
+    assert!(record.is_live(slot, DEFAULT_RECORD_TTL_SLOTS));
+    let expired = record.last_sync_slot() + DEFAULT_RECORD_TTL_SLOTS + 1;
+    assert!(!record.is_live(expired, DEFAULT_RECORD_TTL_SLOTS));
+}
+
+#[tokio::test]
+async fn test_heartbeat_v0_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup_with(common::add_record).await;
+    let pda = record.pda().0;
+
+    let result = common::heartbeat(&mut banks, &identity).await;
+    assert_ok!(result, "error processing heartbeat transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert!(record.is_latest(), "legacy record hasn't been migrated");
+    assert_ne!(record.last_sync_slot(), 0, "legacy record isn't live");
+}
+
+#[tokio::test]
+async fn test_heartbeat_identity_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut ix = mdp::client::heartbeat_ix(identity.pubkey());
+    ix.accounts[0].pubkey = intruder.pubkey();
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+}
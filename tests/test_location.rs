@@ -0,0 +1,109 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    instructions::{sync::SyncInstruction, version::v1::SyncRecordV1},
+    state::{location::Coordinates, record::ErRecord},
+};
+use solana_keypair::Keypair;
+use solana_program_test::{BanksClient, BanksClientError};
+use solana_signer::Signer;
+
+pub mod common;
+
+async fn sync_location(
+    banks: &mut BanksClient,
+    identity: &Keypair,
+    coordinates: Option<Option<Coordinates>>,
+    region: Option<Option<String>>,
+) -> Result<(), BanksClientError> {
+    let ix = client::sync_ix(SyncInstruction::V1(SyncRecordV1 {
+        identity: identity.pubkey(),
+        expected_seq: None,
+        authority: None,
+        status: None,
+        block_time_ms: None,
+        base_fee: None,
+        features: None,
+        load_average: None,
+        country_code: None,
+        coordinates,
+        region,
+        addr: None,
+        endpoints: Vec::new(),
+    }));
+    common::send(banks, ix, identity, &[identity]).await
+}
+
+#[tokio::test]
+async fn test_sync_location() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let result = common::migrate(&mut banks, &identity).await;
+    assert_ok!(result, "error processing migrate transaction {}");
+
+    let frankfurt = Coordinates::from_degrees(50.110924, 8.682127).unwrap();
+    let region = "eu-central-1".to_string();
+    let result = sync_location(
+        &mut banks,
+        &identity,
+        Some(Some(frankfurt)),
+        Some(Some(region)),
+    )
+    .await;
+    assert_ok!(result, "error processing sync location transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.coordinates(), Some(frankfurt));
+    assert_eq!(record.region(), Some("eu-central-1"));
+    let berlin = Coordinates::from_degrees(52.520008, 13.404954).unwrap();
+    let distance = record.distance_km(&berlin).unwrap();
+    assert!((distance - 424.0).abs() < 5.0, "{distance}");
+
+    let invalid = Coordinates {
+        lat: 91_000_000,
+        lon: 0,
+    };
+    let result = sync_location(&mut banks, &identity, Some(Some(invalid)), None).await;
+    assert_mdp_err!(result, MdpError::InvalidLocation);
+
+    let region = "eu central".to_string();
+    let result = sync_location(&mut banks, &identity, None, Some(Some(region))).await;
+    assert_mdp_err!(result, MdpError::InvalidLocation);
+
+    // the disclosed location can be withdrawn
+    let result = sync_location(&mut banks, &identity, Some(None), Some(None)).await;
+    assert_ok!(result, "error processing sync location transaction {}");
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.coordinates(), None);
+    assert_eq!(record.region(), None);
+}
+
+#[tokio::test]
+async fn test_sync_location_legacy_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup_with(common::add_record).await;
+    let pda = record.pda().0;
+
+    let region = "eu-central-1".to_string();
+    let result = sync_location(&mut banks, &identity, None, Some(Some(region.clone()))).await;
+    assert_ok!(result, "error processing sync location transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.region(), Some(region.as_str()));
+}
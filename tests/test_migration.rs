@@ -0,0 +1,110 @@
+use common::TestEnv;
+use mdp::{
+    consts::ER_RECORD_DISCRIMINATOR,
+    error::MdpError,
+    state::{record::ErRecord, version::v1::RecordV1},
+};
+use solana_account::Account;
+use solana_instruction::{AccountMeta, Instruction as SolanaInstruction};
+use solana_signer::Signer;
+
+pub mod common;
+
+#[tokio::test]
+async fn test_migration() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup_with(common::add_record).await;
+    let pda = record.pda().0;
+    let balance = banks.get_balance(pda).await.unwrap();
+
+    let result = common::migrate(&mut banks, &identity).await;
+    assert_ok!(result, "error processing migrate transaction {}");
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(result, "error querying migrated PDA from banks {}");
+    assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
+    let acc = acc.unwrap();
+    assert!(acc.lamports > balance, "rent hasn't been topped up");
+    let result = ErRecord::try_from_account_data(&acc.data);
+    let migrated = assert_ok!(result, "error deserializing migrated record {}");
+    let ErRecord::V1(migrated) = migrated else {
+        panic!("record hasn't been migrated to the latest version");
+    };
+    let ErRecord::V0(expected) = common::record(&identity.pubkey()) else {
+        unreachable!()
+    };
+    let expected = RecordV1 {
+        last_sync_slot: migrated.last_sync_slot,
+        last_sync_unix_ts: migrated.last_sync_unix_ts,
+        ..RecordV1::from(expected)
+    };
+    assert_eq!(migrated, expected);
+    assert_ne!(migrated.last_sync_unix_ts, 0, "migrated record isn't live");
+    assert_eq!(migrated.authority, identity.pubkey());
+}
+
+#[tokio::test]
+async fn test_migration_legacy_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        mut context,
+        ..
+    } = common::setup_with(common::add_legacy_record).await;
+    let pda = record.pda().0;
+
+    let result = common::migrate(&mut banks, &identity).await;
+    assert_ok!(result, "error processing migrate transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    assert!(acc.data.starts_with(&ER_RECORD_DISCRIMINATOR));
+    let result = ErRecord::try_from_account_data(&acc.data);
+    let migrated = assert_ok!(result, "error deserializing migrated record {}");
+    assert!(migrated.is_latest());
+    assert_eq!(migrated.addr(), record.addr());
+
+    // migrating the latest record is a no-op, which doesn't even refresh the liveness
+    context
+        .warp_to_slot(migrated.last_sync_slot() + 10)
+        .unwrap();
+    let result = common::migrate(&mut banks, &identity).await;
+    assert_ok!(result, "error processing repeated migrate transaction {}");
+    let unchanged = banks.get_account(pda).await.unwrap().unwrap();
+    assert_eq!(unchanged, acc);
+}
+
+#[tokio::test]
+async fn test_migration_identity_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut ix = mdp::client::migrate_ix(identity.pubkey());
+    ix.accounts[0] = AccountMeta::new(intruder.pubkey(), true);
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+
+    let ix = SolanaInstruction {
+        accounts: vec![
+            AccountMeta::new(intruder.pubkey(), true),
+            AccountMeta::new(pda, false),
+            AccountMeta::new_readonly(solana_pubkey::Pubkey::default(), false),
+        ],
+        ..mdp::client::migrate_ix(intruder.pubkey())
+    };
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+}
@@ -0,0 +1,105 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    instructions::{sync::SyncInstruction, version::v0::SyncRecordV0},
+};
+use solana_account::Account;
+use solana_instruction::AccountMeta;
+use solana_keypair::Keypair;
+use solana_native_token::LAMPORTS_PER_SOL;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+pub mod common;
+
+async fn setup() -> (TestEnv, Keypair) {
+    let treasury = Keypair::new();
+    let pubkey = treasury.pubkey();
+    let env = common::setup_with(|test, _| {
+        test.add_account(
+            pubkey,
+            Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+        );
+    })
+    .await;
+    (env, treasury)
+}
+
+#[tokio::test]
+async fn test_separate_payer() {
+    let (
+        TestEnv {
+            mut banks,
+            identity,
+            record,
+            ..
+        },
+        treasury,
+    ) = setup().await;
+    let pda = record.pda().0;
+    let identity_balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    let treasury_balance = banks.get_balance(treasury.pubkey()).await.unwrap();
+
+    let ix = client::with_payer(client::register_ix(record), treasury.pubkey());
+    let result = common::send(&mut banks, ix, &treasury, &[&treasury, &identity]).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let rent = banks.get_balance(pda).await.unwrap();
+    let balance = banks.get_balance(treasury.pubkey()).await.unwrap();
+    assert_eq!(balance, treasury_balance - rent - 10_000);
+
+    let sync = SyncInstruction::V0(SyncRecordV0 {
+        identity: identity.pubkey(),
+        addr: Some("https://very-long-url.address.com:42425".to_string()),
+        status: None,
+        block_time_ms: None,
+        base_fee: None,
+        features: None,
+        load_average: None,
+        country_code: None,
+    });
+    let ix = client::with_payer(client::sync_ix(sync), treasury.pubkey());
+    let result = common::send(&mut banks, ix, &treasury, &[&treasury, &identity]).await;
+    assert_ok!(result, "error processing sync transaction {}");
+
+    let topup = banks.get_balance(pda).await.unwrap() - rent;
+    assert!(topup > 0, "rent hasn't been topped up");
+    let balance = banks.get_balance(treasury.pubkey()).await.unwrap();
+    assert_eq!(balance, treasury_balance - rent - topup - 20_000);
+
+    let ix = client::with_payer(client::unregister_ix(identity.pubkey()), treasury.pubkey());
+    let result = common::send(&mut banks, ix, &treasury, &[&treasury, &identity]).await;
+    assert_ok!(result, "error processing unregister transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap();
+    assert!(
+        acc.is_none(),
+        "registration PDA hasn't been removed from banks"
+    );
+    let balance = banks.get_balance(treasury.pubkey()).await.unwrap();
+    assert_eq!(balance, treasury_balance - 30_000);
+    let balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    assert_eq!(
+        balance, identity_balance,
+        "ER identity has paid for the record"
+    );
+}
+
+#[tokio::test]
+async fn test_separate_payer_missing_signature() {
+    let (
+        TestEnv {
+            mut banks,
+            identity,
+            record,
+            ..
+        },
+        treasury,
+    ) = setup().await;
+
+    let mut ix = client::with_payer(client::register_ix(record), treasury.pubkey());
+    ix.accounts[3] = AccountMeta::new(treasury.pubkey(), false);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::MissingSignature);
+}
@@ -0,0 +1,145 @@
+use common::TestEnv;
+use mdp::{
+    consts::{DEFAULT_REAP_BOUNTY_BPS, DEFAULT_REAP_GRACE_SLOTS, DEFAULT_RECORD_TTL_SLOTS},
+    error::MdpError,
+    state::config::ProgramConfig,
+};
+use solana_signer::Signer;
+
+pub mod common;
+
+#[tokio::test]
+async fn test_reap() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        mut context,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::RecordNotExpired);
+
+    common::expire(&mut context, pda).await;
+    let mut banks = context.banks_client.clone();
+
+    let rent = banks.get_balance(pda).await.unwrap();
+    let identity_balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    let intruder_balance = banks.get_balance(intruder.pubkey()).await.unwrap();
+
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_ok!(result, "error processing reap transaction {}");
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(result, "error querying reaped PDA from banks {}");
+    assert!(
+        acc.is_none(),
+        "registration PDA hasn't been removed from banks"
+    );
+
+    let bounty = rent * DEFAULT_REAP_BOUNTY_BPS as u64 / 10_000;
+    let balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    assert_eq!(balance, identity_balance + rent - bounty);
+    // the caller also pays the transaction fee
+    let balance = banks.get_balance(intruder.pubkey()).await.unwrap();
+    assert!(balance > intruder_balance + bounty - 10_000);
+}
+
+#[tokio::test]
+async fn test_reap_live_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        mut context,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    common::expire(&mut context, pda).await;
+    let mut banks = context.banks_client.clone();
+
+    let result = common::heartbeat(&mut banks, &identity).await;
+    assert_ok!(result, "error processing heartbeat transaction {}");
+
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::RecordNotExpired);
+}
+
+#[tokio::test]
+async fn test_reap_by_identity() {
+    let TestEnv {
+        mut banks,
+        identity,
+        mut context,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    common::expire(&mut context, pda).await;
+    let mut banks = context.banks_client.clone();
+
+    let rent = banks.get_balance(pda).await.unwrap();
+    let identity_balance = banks.get_balance(identity.pubkey()).await.unwrap();
+
+    // the identity receives both the bounty and the rest of the rent
+    let result = common::reap(&mut banks, &identity, identity.pubkey()).await;
+    assert_ok!(result, "error processing reap transaction {}");
+    let balance = banks.get_balance(identity.pubkey()).await.unwrap();
+    assert!(balance > identity_balance + rent - 10_000);
+}
+
+#[tokio::test]
+async fn test_reap_v0_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority: admin,
+        mut context,
+        ..
+    } = common::setup_with(common::add_record).await;
+
+    // V0 registration is stored in the latest version, so it's subject to liveness rules
+    let record = common::record(&intruder.pubkey());
+    let result = common::register(&mut banks, record, &intruder).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let result = common::reap(&mut banks, &identity, intruder.pubkey()).await;
+    assert_mdp_err!(result, MdpError::RecordNotExpired);
+
+    // while the legacy V0 record gets the grace period, starting with the config initialization
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::RecordNotExpired);
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let result = common::reap(&mut banks, &admin, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::RecordNotExpired);
+
+    let acc = banks.get_account(ProgramConfig::pda().0).await.unwrap();
+    let config = ProgramConfig::try_from_account_data(&acc.unwrap().data).unwrap();
+    let slot =
+        config.initialized_slot.unwrap() + DEFAULT_RECORD_TTL_SLOTS + DEFAULT_REAP_GRACE_SLOTS;
+    context.warp_to_slot(slot + 1).unwrap();
+    let mut banks = context.banks_client.clone();
+
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_ok!(result, "error processing reap transaction {}");
+    let acc = banks
+        .get_account(common::record(&identity.pubkey()).pda().0)
+        .await;
+    assert!(acc.unwrap().is_none(), "legacy record hasn't been reaped");
+}
@@ -1,10 +1,16 @@
-use borsh::BorshDeserialize;
 use common::TestEnv;
-use mdp::state::{
-    features::{Feature, FeaturesSet},
-    record::ErRecord,
+use mdp::{
+    client,
+    consts::ER_RECORD_DISCRIMINATOR,
+    error::MdpError,
+    state::{
+        features::{Feature, FeaturesSet},
+        record::ErRecord,
+        status::Suspension,
+    },
 };
 use solana_account::Account;
+use solana_instruction::AccountMeta;
 use solana_signer::Signer;
 
 pub mod common;
@@ -29,8 +35,11 @@ async fn test_registration() {
     let acc = assert_ok!(result, "error querying registration PDA from banks {}");
     assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
     let acc = acc.unwrap();
-    let result = ErRecord::try_from_slice(&acc.data);
+    assert!(acc.data.starts_with(&ER_RECORD_DISCRIMINATOR));
+    let result = ErRecord::try_from_account_data(&acc.data);
     let record = assert_ok!(result, "error querying registration PDA from banks {}");
+    assert!(record.is_latest(), "registered record hasn't been upgraded");
+    assert_ne!(record.last_sync_slot(), 0, "registered record isn't live");
     assert_eq!(*record.identity(), identity.pubkey());
     assert_eq!(
         *record.features(),
@@ -38,3 +47,134 @@ async fn test_registration() {
     );
     assert_eq!(record.addr(), addr);
 }
+
+#[tokio::test]
+async fn test_registration_resets_managed_fields() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority,
+        ..
+    } = common::setup().await;
+    let record =
+        common::record_with_authority(&identity.pubkey(), &authority.pubkey()).into_latest();
+    let ErRecord::V1(mut forged) = record else {
+        unreachable!()
+    };
+    forged.previous_identity = Some(intruder.pubkey());
+    forged.suspension = Some(Suspension {
+        reason: 1,
+        expires_at: None,
+    });
+    let pda = ErRecord::pda_for(&identity.pubkey()).0;
+
+    let result = common::register(&mut banks, ErRecord::V1(forged), &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.previous_identity(), None);
+    assert!(record.suspension().is_none());
+    assert_eq!(record.seq(), 0);
+    assert_ne!(record.last_sync_slot(), 42);
+    assert_eq!(*record.authority(), authority.pubkey());
+}
+
+#[tokio::test]
+async fn test_registration_identity_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &intruder).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+
+    let record = common::record(&intruder.pubkey());
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+}
+
+#[tokio::test]
+async fn test_registration_missing_signature() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+
+    let mut ix = client::register_ix(record);
+    ix.accounts[0] = AccountMeta::new(identity.pubkey(), false);
+    let result = common::send(&mut banks, ix, &intruder, &[&intruder]).await;
+    assert_mdp_err!(result, MdpError::MissingSignature);
+}
+
+#[tokio::test]
+async fn test_registration_pda_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = common::record(&intruder.pubkey()).pda().0;
+
+    let mut ix = client::register_ix(record);
+    ix.accounts[1] = AccountMeta::new(pda, false);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::PdaMismatch);
+}
+
+#[tokio::test]
+async fn test_registration_duplicate() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut duplicate = common::record(&identity.pubkey());
+    duplicate.set_block_time_ms(100);
+    let result = common::register(&mut banks, duplicate, &identity).await;
+    assert_mdp_err!(result, MdpError::RecordAlreadyExists);
+}
+
+#[tokio::test]
+async fn test_registration_invalid_addr() {
+    let TestEnv {
+        mut banks,
+        identity,
+        mut record,
+        ..
+    } = common::setup().await;
+
+    record.set_addr(" https://241.132.2.41:9324/".to_string());
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_mdp_err!(result, MdpError::InvalidAddr);
+}
+
+#[tokio::test]
+async fn test_registration_invalid_country_code() {
+    let TestEnv {
+        mut banks,
+        identity,
+        mut record,
+        ..
+    } = common::setup().await;
+
+    let country_code = borsh::from_slice(b"083").unwrap();
+    record.set_country_code(country_code);
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_mdp_err!(result, MdpError::InvalidCountryCode);
+}
@@ -0,0 +1,110 @@
+use common::TestEnv;
+use mdp::{error::MdpError, state::record::ErRecord};
+use solana_account::Account;
+use solana_instruction::AccountMeta;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+pub mod common;
+
+#[tokio::test]
+async fn test_identity_rotation() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+    let new_identity = Keypair::new();
+    let new_pda = ErRecord::pda_for(&new_identity.pubkey()).0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let registered = ErRecord::try_from_account_data(&acc.data).unwrap();
+
+    let result = common::rotate_identity(&mut banks, &identity, &new_identity).await;
+    assert_ok!(result, "error processing rotate identity transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap();
+    assert!(
+        acc.is_none(),
+        "old record PDA hasn't been removed from banks"
+    );
+
+    let result = banks.get_account(new_pda).await;
+    let acc = assert_ok!(result, "error querying rotated PDA from banks {}");
+    assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
+    let result = ErRecord::try_from_account_data(&acc.unwrap().data);
+    let rotated = assert_ok!(result, "error deserializing rotated record {}");
+    assert!(rotated.is_latest());
+    assert_eq!(rotated.identity(), &new_identity.pubkey());
+    assert_eq!(rotated.authority(), &new_identity.pubkey());
+    assert_eq!(rotated.previous_identity(), Some(&identity.pubkey()));
+    assert_eq!(rotated.addr(), registered.addr());
+    assert_eq!(rotated.seq(), registered.seq());
+    assert_eq!(rotated.registered_at(), registered.registered_at());
+}
+
+#[tokio::test]
+async fn test_identity_rotation_keeps_authority() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority,
+        ..
+    } = common::setup().await;
+    let record = common::record_with_authority(&identity.pubkey(), &authority.pubkey());
+    let new_identity = Keypair::new();
+    let new_pda = ErRecord::pda_for(&new_identity.pubkey()).0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let result = common::rotate_identity(&mut banks, &identity, &new_identity).await;
+    assert_ok!(result, "error processing rotate identity transaction {}");
+
+    let acc = banks.get_account(new_pda).await.unwrap().unwrap();
+    let rotated = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(rotated.authority(), &authority.pubkey());
+}
+
+#[tokio::test]
+async fn test_identity_rotation_missing_signature() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let new_identity = Keypair::new();
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut ix = mdp::client::rotate_identity_ix(identity.pubkey(), new_identity.pubkey());
+    ix.accounts[1] = AccountMeta::new_readonly(new_identity.pubkey(), false);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::MissingSignature);
+}
+
+#[tokio::test]
+async fn test_identity_rotation_existing_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let other = common::record(&intruder.pubkey());
+    let result = common::register(&mut banks, other, &intruder).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let result = common::rotate_identity(&mut banks, &identity, &intruder).await;
+    assert_mdp_err!(result, MdpError::RecordAlreadyExists);
+}
@@ -0,0 +1,143 @@
+use common::TestEnv;
+use mdp::{
+    client,
+    error::MdpError,
+    state::{
+        record::ErRecord,
+        status::{ErStatus, Suspension},
+    },
+};
+use solana_program_test::BanksClient;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+pub mod common;
+
+async fn fetch(banks: &mut BanksClient, pda: Pubkey) -> ErRecord {
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    ErRecord::try_from_account_data(&acc.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_suspension() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority: admin,
+        mut record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+    let suspension = Suspension {
+        reason: 7,
+        expires_at: None,
+    };
+
+    let result = common::register(&mut banks, common::record(&identity.pubkey()), &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+
+    let result = common::suspend(&mut banks, &intruder, identity.pubkey(), suspension).await;
+    assert_mdp_err!(result, MdpError::AdminMismatch);
+    let result = common::suspend(&mut banks, &admin, identity.pubkey(), suspension).await;
+    assert_ok!(result, "error suspending ER node {}");
+
+    // operator can't lift the suspension
+    record.set_status(ErStatus::Active);
+    record.set_block_time_ms(100);
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_ok!(result, "error processing sync transaction {}");
+    let synced = fetch(&mut banks, pda).await;
+    assert_eq!(synced.suspension(), Some(&suspension));
+    assert!(!synced.is_routable(synced.last_sync_slot(), synced.last_sync_unix_ts(), 1));
+
+    let result = common::unregister(&mut banks, &identity, pda).await;
+    assert_mdp_err!(result, MdpError::Suspended);
+
+    let ix = client::unsuspend_ix(admin.pubkey(), identity.pubkey());
+    let result = common::send(&mut banks, ix, &admin, &[&admin]).await;
+    assert_ok!(result, "error lifting suspension {}");
+    let record = fetch(&mut banks, pda).await;
+    assert_eq!(record.suspension(), None);
+    assert!(record.is_routable(record.last_sync_slot(), record.last_sync_unix_ts(), 1));
+
+    // different fee payer, so that the transaction isn't deduplicated
+    let ix = client::unregister_ix(identity.pubkey());
+    let result = common::send(&mut banks, ix, &admin, &[&admin, &identity]).await;
+    assert_ok!(result, "error processing unregister transaction {}");
+}
+
+#[tokio::test]
+async fn test_expired_suspension() {
+    let TestEnv {
+        mut banks,
+        identity,
+        authority: admin,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+
+    let now = fetch(&mut banks, pda).await.registered_at();
+    let suspension = Suspension {
+        reason: 1,
+        expires_at: Some(now),
+    };
+    let result = common::suspend(&mut banks, &admin, identity.pubkey(), suspension).await;
+    assert_ok!(result, "error suspending ER node {}");
+    let record = fetch(&mut banks, pda).await;
+    assert!(!record.is_suspended(now));
+    assert!(record.suspension().is_some());
+
+    // expired suspension is lifted on the next sync
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_ok!(result, "error processing sync transaction {}");
+    assert_eq!(fetch(&mut banks, pda).await.suspension(), None);
+}
+
+#[tokio::test]
+async fn test_reap_suspended_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority: admin,
+        record,
+        mut context,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+    let suspension = Suspension {
+        reason: 3,
+        expires_at: None,
+    };
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let result = common::init_config(&mut banks, &admin).await;
+    assert_ok!(result, "error initializing config {}");
+    let result = common::suspend(&mut banks, &admin, identity.pubkey(), suspension).await;
+    assert_ok!(result, "error suspending ER node {}");
+
+    // the operator can't get rid of the suspension by letting the record expire
+    common::expire(&mut context, pda).await;
+    let mut banks = context.banks_client.clone();
+    let result = common::reap(&mut banks, &intruder, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::Suspended);
+
+    let ix = client::unsuspend_ix(admin.pubkey(), identity.pubkey());
+    let result = common::send(&mut banks, ix, &admin, &[&admin]).await;
+    assert_ok!(result, "error lifting suspension {}");
+    // different fee payer, so that the transaction isn't deduplicated
+    let ix = client::reap_ix(intruder.pubkey(), identity.pubkey());
+    let result = common::send(&mut banks, ix, &admin, &[&admin, &intruder]).await;
+    assert_ok!(result, "error processing reap transaction {}");
+    assert!(banks.get_account(pda).await.unwrap().is_none());
+}
@@ -1,7 +1,17 @@
-use borsh::BorshDeserialize;
 use common::TestEnv;
-use mdp::state::{record::ErRecord, status::ErStatus};
+use mdp::{
+    client,
+    consts::ER_RECORD_DISCRIMINATOR,
+    error::MdpError,
+    instructions::{sync::SyncInstruction, version::v1::SyncRecordV1},
+    state::{
+        record::{CountryCode, ErRecord},
+        status::ErStatus,
+    },
+};
 use solana_account::Account;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
 
 pub mod common;
 
@@ -23,7 +33,7 @@ async fn test_sync_info() {
     let acc = assert_ok!(result, "error querying registration PDA from banks {}");
     assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
     let acc = acc.unwrap();
-    let result = ErRecord::try_from_slice(&acc.data);
+    let result = ErRecord::try_from_account_data(&acc.data);
     let mut record = assert_ok!(result, "error querying registration PDA from banks {}");
 
     const NEW_BLOCK_TIME: u16 = 1000;
@@ -45,7 +55,7 @@ async fn test_sync_info() {
     );
     assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
     let acc = acc.unwrap();
-    let result = ErRecord::try_from_slice(&acc.data);
+    let result = ErRecord::try_from_account_data(&acc.data);
     let record = assert_ok!(
         result,
         "error querying registration PDA post modification {}"
@@ -86,7 +96,7 @@ async fn test_sync_info_with_realloc() {
     let acc = assert_ok!(result, "error querying registration PDA from banks {}");
     assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
     let acc = acc.unwrap();
-    let result = ErRecord::try_from_slice(&acc.data);
+    let result = ErRecord::try_from_account_data(&acc.data);
     let mut record = assert_ok!(result, "error querying registration PDA from banks {}");
 
     const NEW_BLOCK_TIME: u16 = 1000;
@@ -108,10 +118,309 @@ async fn test_sync_info_with_realloc() {
     );
     assert!(matches!(acc, Some(Account { owner: mdp::ID, .. })));
     let acc = acc.unwrap();
-    let result = ErRecord::try_from_slice(&acc.data);
+    let result = ErRecord::try_from_account_data(&acc.data);
     let record = assert_ok!(
         result,
         "error querying registration PDA post modification {}"
     );
     assert_eq!(record.addr(), NEW_ADDR);
 }
+
+#[tokio::test]
+async fn test_sync_unregistered() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_mdp_err!(result, MdpError::InvalidRecordOwner);
+}
+
+#[tokio::test]
+async fn test_sync_identity_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut record = common::record(&identity.pubkey());
+    record.set_status(ErStatus::Offline);
+    let result = common::sync(&mut banks, &intruder, record).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+}
+
+#[tokio::test]
+async fn test_sync_legacy_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup_with(common::add_legacy_record).await;
+    let pda = record.pda().0;
+
+    let mut record = common::record(&identity.pubkey());
+    record.set_load_average(42);
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_ok!(result, "error processing sync info transaction {}");
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(
+        result,
+        "error querying registration PDA post modification {}"
+    );
+    let acc = acc.unwrap();
+    assert!(
+        acc.data.starts_with(&ER_RECORD_DISCRIMINATOR),
+        "legacy record hasn't been upgraded during sync"
+    );
+    let result = ErRecord::try_from_account_data(&acc.data);
+    let record = assert_ok!(result, "error deserializing upgraded record {}");
+    assert_eq!(record.load_average(), 42);
+}
+
+#[tokio::test]
+async fn test_sync_stamps_freshness() {
+    let TestEnv {
+        mut banks,
+        identity,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(result, "error querying registration PDA from banks {}");
+    let result = ErRecord::try_from_account_data(&acc.unwrap().data);
+    let registered = assert_ok!(result, "error deserializing registered record {}");
+    assert_ne!(registered.registered_at(), 42);
+    assert_eq!(registered.last_sync_unix_ts(), registered.registered_at());
+    assert_eq!(registered.seq(), 0);
+
+    for seq in 1..=2 {
+        let mut record = common::record_v1(&identity.pubkey());
+        record.set_load_average(seq as u32);
+        let result = common::sync(&mut banks, &identity, record).await;
+        assert_ok!(result, "error processing sync info transaction {}");
+
+        let result = banks.get_account(pda).await;
+        let acc = assert_ok!(
+            result,
+            "error querying registration PDA post modification {}"
+        );
+        let result = ErRecord::try_from_account_data(&acc.unwrap().data);
+        let record = assert_ok!(result, "error deserializing synced record {}");
+        assert_eq!(record.seq(), seq);
+        assert_eq!(record.registered_at(), registered.registered_at());
+        assert!(record.last_sync_slot() >= registered.last_sync_slot());
+        assert!(record.last_sync_unix_ts() >= registered.last_sync_unix_ts());
+    }
+}
+
+#[tokio::test]
+async fn test_sync_compare_and_swap() {
+    let TestEnv {
+        mut banks,
+        identity,
+        ..
+    } = common::setup().await;
+    let record = common::record_v1(&identity.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut record = common::record_v1(&identity.pubkey());
+    record.set_load_average(1);
+    let result = common::sync_cas(&mut banks, &identity, record, 0).await;
+    assert_ok!(result, "error processing sync info transaction {}");
+
+    // a concurrent writer, which has observed the same sequence number
+    let mut record = common::record_v1(&identity.pubkey());
+    record.set_load_average(2);
+    let result = common::sync_cas(&mut banks, &identity, record, 0).await;
+    assert_mdp_err!(result, MdpError::SeqMismatch);
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.load_average(), 1);
+    assert_eq!(record.seq(), 1);
+}
+
+#[tokio::test]
+async fn test_sync_compare_and_swap_v0_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        ..
+    } = common::setup_with(common::add_record).await;
+
+    // legacy record is upgraded by the sync, starting its sequence from 0
+    let mut record = common::record(&identity.pubkey());
+    record.set_load_average(1);
+    let pda = record.pda().0;
+    let result = common::sync_cas(&mut banks, &identity, record, 0).await;
+    assert_ok!(result, "error processing sync transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.load_average(), 1);
+    assert_eq!(record.seq(), 1);
+}
+
+#[tokio::test]
+async fn test_sync_by_authority() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority,
+        ..
+    } = common::setup().await;
+    let record = || common::record_with_authority(&identity.pubkey(), &authority.pubkey());
+    let pda = record().pda().0;
+
+    let result = common::register(&mut banks, record(), &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut update = record();
+    update.set_status(ErStatus::Draining);
+    let result = common::authority_sync(&mut banks, &authority, update).await;
+    assert_ok!(result, "error processing authority sync transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let synced = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(synced.status(), ErStatus::Draining);
+    assert_eq!(synced.authority(), &authority.pubkey());
+
+    // the identity can still manage the record on its own
+    let mut update = record();
+    update.set_status(ErStatus::Active);
+    let result = common::sync(&mut banks, &identity, update).await;
+    assert_ok!(result, "error processing sync transaction {}");
+
+    let mut update = record();
+    update.set_status(ErStatus::Offline);
+    let result = common::authority_sync(&mut banks, &intruder, update).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+}
+
+#[tokio::test]
+async fn test_sync_invalid_addr() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut record = common::record(&identity.pubkey());
+    record.set_addr("ftp://241.132.2.41:9324/".to_string());
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_mdp_err!(result, MdpError::InvalidAddr);
+}
+
+#[tokio::test]
+async fn test_sync_invalid_country_code() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let mut record = common::record(&identity.pubkey());
+    record.set_country_code(borsh::from_slice(b"XYZ").unwrap());
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_mdp_err!(result, MdpError::InvalidCountryCode);
+}
+
+#[tokio::test]
+async fn test_sync_country_code() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let country_code = CountryCode::try_from("JP").unwrap();
+    let mut record = common::record(&identity.pubkey());
+    record.set_country_code(country_code);
+    let result = common::sync(&mut banks, &identity, record).await;
+    assert_ok!(result, "error processing sync transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.country_code(), country_code);
+}
+
+fn authority_sync(identity: Pubkey, authority: Pubkey) -> SyncInstruction {
+    SyncInstruction::V1(SyncRecordV1 {
+        identity,
+        expected_seq: None,
+        authority: Some(authority),
+        status: None,
+        block_time_ms: None,
+        base_fee: None,
+        features: None,
+        load_average: None,
+        country_code: None,
+        coordinates: None,
+        region: None,
+        addr: None,
+        endpoints: Vec::new(),
+    })
+}
+
+#[tokio::test]
+async fn test_sync_authority() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority,
+        ..
+    } = common::setup().await;
+    let record = common::record_with_authority(&identity.pubkey(), &authority.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    // authority can't hand over the record on its own
+    let sync = authority_sync(identity.pubkey(), intruder.pubkey());
+    let ix = client::authority_sync_ix(authority.pubkey(), sync);
+    let result = common::send(&mut banks, ix, &authority, &[&authority]).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+
+    let ix = client::sync_ix(authority_sync(identity.pubkey(), intruder.pubkey()));
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_ok!(result, "error processing sync transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap().unwrap();
+    let record = ErRecord::try_from_account_data(&acc.data).unwrap();
+    assert_eq!(record.authority(), &intruder.pubkey());
+}
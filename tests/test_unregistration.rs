@@ -1,4 +1,8 @@
 use common::TestEnv;
+use mdp::{client, error::MdpError};
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
 
 pub mod common;
 
@@ -27,3 +31,147 @@ async fn test_unregistration() {
         "registration PDA hasn't been removed from banks"
     );
 }
+
+#[tokio::test]
+async fn test_unregistration_unregistered() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+
+    let result = common::unregister(&mut banks, &identity, pda).await;
+    assert_mdp_err!(result, MdpError::InvalidRecordOwner);
+}
+
+#[tokio::test]
+async fn test_unregistration_identity_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let result = common::unregister(&mut banks, &intruder, pda).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(result, "error querying registration PDA from banks {}");
+    assert!(
+        acc.is_some(),
+        "registration PDA has been removed by intruder"
+    );
+}
+
+#[tokio::test]
+async fn test_unregistration_legacy_record() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup_with(common::add_legacy_record).await;
+    let pda = record.pda().0;
+
+    let result = common::unregister(&mut banks, &identity, pda).await;
+    assert_ok!(result, "error processing unregister transaction {}");
+
+    let result = banks.get_account(pda).await;
+    let acc = assert_ok!(result, "error querying unregistered PDA from banks {}");
+    assert!(
+        acc.is_none(),
+        "registration PDA hasn't been removed from banks"
+    );
+}
+
+#[tokio::test]
+async fn test_unregistration_by_authority() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        authority,
+        ..
+    } = common::setup().await;
+    let record = common::record_with_authority(&identity.pubkey(), &authority.pubkey());
+    let pda = record.pda().0;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let result = common::authority_unregister(&mut banks, &intruder, identity.pubkey()).await;
+    assert_mdp_err!(result, MdpError::IdentityMismatch);
+
+    let rent = banks.get_balance(pda).await.unwrap();
+    let balance = banks.get_balance(authority.pubkey()).await.unwrap();
+    let result = common::authority_unregister(&mut banks, &authority, identity.pubkey()).await;
+    assert_ok!(
+        result,
+        "error processing authority unregister transaction {}"
+    );
+
+    let acc = banks.get_account(pda).await.unwrap();
+    assert!(
+        acc.is_none(),
+        "registration PDA hasn't been removed from banks"
+    );
+    let received = banks.get_balance(authority.pubkey()).await.unwrap() + 5_000 - balance;
+    assert_eq!(received, rent, "rent hasn't been returned to the authority");
+}
+
+#[tokio::test]
+async fn test_unregistration_with_recipient() {
+    let TestEnv {
+        mut banks,
+        identity,
+        record,
+        ..
+    } = common::setup().await;
+    let pda = record.pda().0;
+    let recipient = Pubkey::new_unique();
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+    let rent = banks.get_balance(pda).await.unwrap();
+
+    let ix = client::unregister_with_recipient_ix(identity.pubkey(), identity.pubkey(), recipient);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_ok!(result, "error processing unregister transaction {}");
+
+    let acc = banks.get_account(pda).await.unwrap();
+    assert!(
+        acc.is_none(),
+        "registration PDA hasn't been removed from banks"
+    );
+    let balance = banks.get_balance(recipient).await.unwrap();
+    assert_eq!(balance, rent, "rent hasn't been sent to the recipient");
+}
+
+#[tokio::test]
+async fn test_unregistration_recipient_mismatch() {
+    let TestEnv {
+        mut banks,
+        identity,
+        intruder,
+        record,
+        ..
+    } = common::setup().await;
+
+    let result = common::register(&mut banks, record, &identity).await;
+    assert_ok!(result, "error processing register transaction {}");
+
+    let recipient = Pubkey::new_unique();
+    let mut ix =
+        client::unregister_with_recipient_ix(identity.pubkey(), identity.pubkey(), recipient);
+    ix.accounts[3] = AccountMeta::new(intruder.pubkey(), false);
+    let result = common::send(&mut banks, ix, &identity, &[&identity]).await;
+    assert_mdp_err!(result, MdpError::RecipientMismatch);
+}